@@ -0,0 +1,71 @@
+import org.agrona.concurrent.UnsafeBuffer;
+import org.agrona.concurrent.ringbuffer.OneToOneRingBuffer;
+import org.agrona.concurrent.ringbuffer.RingBuffer;
+
+import java.io.IOException;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+
+import static org.agrona.BitUtil.SIZE_OF_INT;
+import static org.agrona.BitUtil.SIZE_OF_LONG;
+import static org.agrona.concurrent.ringbuffer.RingBufferDescriptor.TRAILER_LENGTH;
+
+/**
+ * Writes the golden ring buffer files checked by Ringo's tests/ring_buffer_layout.rs.
+ * Run through regenerate-fixtures.sh, or with the Agrona jar on the classpath:
+ * java AgronaRingBufferFixtures ../../tests/fixtures
+ */
+public class AgronaRingBufferFixtures {
+
+    public static void main(final String[] args) throws IOException {
+        if (args.length != 1) {
+            System.err.println("Usage: AgronaRingBufferFixtures <fixturesDir>");
+            return;
+        }
+        final Path dir = Paths.get(args[0]);
+        Files.write(dir.resolve("one_to_one_written.bin"), written());
+        Files.write(dir.resolve("one_to_one_wrapped.bin"), wrapped());
+    }
+
+    private static byte[] written() {
+        final UnsafeBuffer buffer = new UnsafeBuffer(new byte[1024 + TRAILER_LENGTH]);
+        final RingBuffer ringBuffer = new OneToOneRingBuffer(buffer);
+        final UnsafeBuffer src = new UnsafeBuffer(new byte[2 * SIZE_OF_LONG]);
+
+        src.putInt(0, 42);
+        ringBuffer.write(1, src, 0, SIZE_OF_INT);
+        src.putLong(0, 0x0102030405060708L);
+        ringBuffer.write(2, src, 0, SIZE_OF_LONG);
+
+        final int index = ringBuffer.tryClaim(3, 2 * SIZE_OF_LONG);
+        buffer.putLong(index, 7);
+        buffer.putLong(index + SIZE_OF_LONG, -1);
+        ringBuffer.commit(index);
+
+        ringBuffer.consumerHeartbeatTime(1234567890L);
+        return buffer.byteArray();
+    }
+
+    private static byte[] wrapped() {
+        final UnsafeBuffer buffer = new UnsafeBuffer(new byte[128 + TRAILER_LENGTH]);
+        final RingBuffer ringBuffer = new OneToOneRingBuffer(buffer);
+        final UnsafeBuffer src = new UnsafeBuffer(new byte[2 * SIZE_OF_LONG]);
+
+        for (long seq = 1; seq <= 5; seq++) {
+            src.putLong(0, seq);
+            ringBuffer.write(1, src, 0, SIZE_OF_LONG);
+        }
+        ringBuffer.read((msgTypeId, msgBuffer, index, length) -> {});
+
+        src.putLong(0, 6);
+        ringBuffer.write(2, src, 0, SIZE_OF_LONG);
+        src.putLong(0, 7);
+        src.putLong(SIZE_OF_LONG, 8);
+        ringBuffer.write(3, src, 0, 2 * SIZE_OF_LONG);
+        src.putLong(0, 9);
+        ringBuffer.write(4, src, 0, SIZE_OF_LONG); // wraps, leaving a padding record at the end
+
+        return buffer.byteArray();
+    }
+}
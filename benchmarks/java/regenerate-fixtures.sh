@@ -0,0 +1,18 @@
+#!/bin/sh
+# Regenerate tests/fixtures/one_to_one_*.bin by running AgronaRingBufferFixtures against a
+# real Agrona jar. Set AGRONA_JAR to use a local jar instead of downloading AGRONA_VERSION.
+set -eu
+
+AGRONA_VERSION="${AGRONA_VERSION:-1.21.2}"
+cd "$(dirname "$0")"
+work="$(mktemp -d)"
+trap 'rm -rf "$work"' EXIT
+
+jar="${AGRONA_JAR:-$work/agrona-$AGRONA_VERSION.jar}"
+if [ ! -f "$jar" ]; then
+    curl -sSfLo "$jar" "https://repo1.maven.org/maven2/org/agrona/agrona/$AGRONA_VERSION/agrona-$AGRONA_VERSION.jar"
+fi
+
+javac -cp "$jar" -d "$work" AgronaRingBufferFixtures.java
+java -cp "$jar:$work" AgronaRingBufferFixtures ../../tests/fixtures
+echo "wrote tests/fixtures with $(basename "$jar"), update the version in tests/ring_buffer_layout.rs to match"
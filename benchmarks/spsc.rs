@@ -3,8 +3,13 @@ use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
 use hdrhistogram::Histogram;
 use std::collections::HashSet;
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
+use Ringo::agrona::concurrent::busy_spin_idle_strategy::BusySpinIdleStrategy;
+use Ringo::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use Ringo::agrona::concurrent::idle_strategy::IdleStrategy;
+use Ringo::agrona::concurrent::nano_clock::{NanoClock, SystemNanoClock};
 use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::thread_affinity::ThreadAffinity;
 use Ringo::agrona::concurrent::ringbuffer::ring_buffer::RingBuffer;
 use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
 use Ringo::agrona::direct_buffer::DirectBuffer;
@@ -44,11 +49,11 @@ fn spsc_chan(cap: Option<usize>) {
 
         let mut ctr = 0;
         let mut histogram = Histogram::<u64>::new(3).unwrap();
-        let mut ori_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+        let mut ori_ms = SystemEpochClock.time();
 
         loop {
             if ctr < MAX_IN_FLIGHTS as usize {
-                let ts = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos() as i64;
+                let ts = SystemNanoClock.nano_time();
                 // let msg = Arc::new(MyClass::new(seq, ts));
                 // let msg = Box::new(MyClass::new(seq, ts));
                 if tx1.try_send(ts).is_ok() {
@@ -58,9 +63,7 @@ fn spsc_chan(cap: Option<usize>) {
             loop {
                 match rx2.try_recv() {
                     Ok(value) => {
-                        let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)
-                            .unwrap()
-                            .as_nanos() as i64 - value;
+                        let elapsed = SystemNanoClock.nano_time() - value;
                         histogram.record(elapsed as u64).unwrap();
                         ctr -= 1;
                     }
@@ -96,11 +99,11 @@ fn spsc(cap: usize) {
 
         let mut ctr = 0;
         let mut histogram = Histogram::<u64>::new(3).unwrap();
-        let mut ori_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+        let mut ori_ms = SystemEpochClock.time();
 
         loop {
             if ctr < MAX_IN_FLIGHTS as usize {
-                let ts = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos() as i64;
+                let ts = SystemNanoClock.nano_time();
                 match q1.try_push(ts) {
                     Ok(_) => {
                         ctr += 1;
@@ -111,9 +114,7 @@ fn spsc(cap: usize) {
             loop {
                 match q2.try_pop() {
                     Some(value) => {
-                        let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)
-                            .unwrap()
-                            .as_nanos() as i64 - value;
+                        let elapsed = SystemNanoClock.nano_time() - value;
                         histogram.record(elapsed as u64).unwrap();
                         ctr -= 1;
                     }
@@ -127,27 +128,20 @@ fn spsc(cap: usize) {
 }
 
 fn write(seq: i64, now_ns: i64, buffer: &OneToOneRingBuffer) -> bool {
-    let idx = buffer.try_claim(1, 2 * SIZE_OF_LONG);
-    // println!("idx: {:?}", idx);
-    if idx > 0 {
-        let buf = buffer.buffer();
-        let mut rb2_offset = idx;
-        buf.put_long(rb2_offset, seq);
-        rb2_offset += SIZE_OF_LONG;
-        buf.put_long(rb2_offset, now_ns);
-        buffer.commit(idx);
+    if let Ok(mut claim) = buffer.claim(1, 2 * SIZE_OF_LONG) {
+        let (seq_bytes, ts_bytes) = claim.split_at_mut(SIZE_OF_LONG as usize);
+        seq_bytes.copy_from_slice(&seq.to_ne_bytes());
+        ts_bytes.copy_from_slice(&now_ns.to_ne_bytes());
+        claim.commit().unwrap();
         return true;
     }
     false
 }
 
 fn write0(now_ns: i64, buffer: &OneToOneRingBuffer) -> bool {
-    let idx = buffer.try_claim(1, SIZE_OF_LONG);
-    // println!("idx: {:?}", idx);
-    if idx > 0 {
-        let buf = buffer.buffer();
-        buf.put_long(idx, now_ns);
-        buffer.commit(idx);
+    if let Ok(mut claim) = buffer.claim(1, SIZE_OF_LONG) {
+        claim.copy_from_slice(&now_ns.to_ne_bytes());
+        claim.commit().unwrap();
         return true;
     }
     false
@@ -161,6 +155,7 @@ fn spsc_own(cap: usize) {
 
     crossbeam::scope(|scope| {
         scope.spawn(|_| {
+            apply_affinity("SPSC_ECHO_AFFINITY");
             let closure = |msg_type: i32, buffer: &UnsafeBuffer, index: i32, length: i32| {
                 // let seq = buffer.get_long(index);
                 // let offset = index + SIZE_OF_LONG;
@@ -171,21 +166,24 @@ fn spsc_own(cap: usize) {
                     }
                 }
             };
+            let mut idle_strategy = BusySpinIdleStrategy;
             loop {
-                rb1.read0(closure, 500);
+                let work_count = rb1.read0(closure, 500);
+                idle_strategy.idle(work_count);
             }
         });
 
+        apply_affinity("SPSC_PRODUCER_AFFINITY");
         // let mut set = HashSet::new();
         // let mut seq : i64 = 1;
         let mut ctr = 0;
         let mut histogram = Histogram::<u64>::new(3).unwrap();
-        let mut ori_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+        let mut ori_ms = SystemEpochClock.time();
 
         loop {
             // thread::sleep(Duration::from_micros(100000));
             if ctr < MAX_IN_FLIGHTS as usize {
-                let ts = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos() as i64;
+                let ts = SystemNanoClock.nano_time();
                 if write0(ts, &rb1) {
                     // set.insert(seq);
                     // println!("Sent: {}", seq);
@@ -197,9 +195,7 @@ fn spsc_own(cap: usize) {
                 // let seq = buffer.get_long(index);
                 // let offset = index + SIZE_OF_LONG;
                 let ts = buffer.get_long(index);
-                let elapsed = SystemTime::now().duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_nanos() as i64 - ts;
+                let elapsed = SystemNanoClock.nano_time() - ts;
                 histogram.record(elapsed as u64).unwrap();
                 // set.remove(&seq);
                 ctr -= 1;
@@ -212,8 +208,16 @@ fn spsc_own(cap: usize) {
     }).unwrap();
 }
 
-fn record_time(histogram: &mut Histogram<u64>, mut ori_ms: u128) -> u128 {
-    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+// e.g. SPSC_ECHO_AFFINITY="cpus=3;fifo=50", see ThreadAffinity
+fn apply_affinity(var: &str) {
+    if let Ok(config) = std::env::var(var) {
+        let affinity: ThreadAffinity = config.parse().unwrap_or_else(|e| panic!("invalid {}={}: {}", var, config, e));
+        affinity.apply().unwrap_or_else(|e| panic!("failed to apply {}={}: {}", var, config, e));
+    }
+}
+
+fn record_time(histogram: &mut Histogram<u64>, mut ori_ms: i64) -> i64 {
+    let now_ms = SystemEpochClock.time();
     if now_ms - ori_ms > 5_000 {
         ori_ms = now_ms;
         let count = histogram.len();
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, process, slice, thread};
+
+use Ringo::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::record_descriptor::{length_offset, type_offset, encoded_msg_offset, ALIGNMENT, HEADER_LENGTH};
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer::{RingBuffer, PADDING_MSG_TYPE_ID};
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_descriptor::CORRELATION_COUNTER_OFFSET;
+use Ringo::agrona::concurrent::status::counters_reader::{CountersReader, COUNTER_LENGTH, METADATA_LENGTH};
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+use Ringo::bit_util;
+
+const USAGE: &str = "\
+usage: inspector [options] <ring-buffer-file>
+       inspector [options] --counters <meta-data-file> <values-file>
+
+options:
+  --watch <ms>     refresh every <ms> milliseconds until interrupted
+  --records <n>    maximum number of records to dump (default 16)
+  --bytes <n>      maximum number of payload bytes to print per record (default 64)";
+
+#[derive(Debug)]
+enum Target {
+    RingBuffer(String),
+    Counters(String, String),
+}
+
+#[derive(Debug)]
+struct Config {
+    target: Target,
+    watch: Option<Duration>,
+    max_records: usize,
+    max_bytes: usize,
+}
+
+impl Config {
+    fn build(args: &[String]) -> Result<Config, String> {
+        let mut watch = None;
+        let mut max_records = 16;
+        let mut max_bytes = 64;
+        let mut files = Vec::new();
+        let mut counters = false;
+
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--watch" => watch = Some(Duration::from_millis(Self::number(arg, args.next())?)),
+                "--records" => max_records = Self::number(arg, args.next())? as usize,
+                "--bytes" => max_bytes = Self::number(arg, args.next())? as usize,
+                "--counters" => counters = true,
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                _ if arg.starts_with("--") => return Err(format!("unknown option: {}\n{}", arg, USAGE)),
+                _ => files.push(arg.clone()),
+            }
+        }
+
+        let target = match (counters, files.as_slice()) {
+            (false, [file]) => Target::RingBuffer(file.clone()),
+            (true, [meta_data_file, values_file]) => Target::Counters(meta_data_file.clone(), values_file.clone()),
+            _ => return Err(USAGE.to_string()),
+        };
+
+        Ok(Config { target, watch, max_records, max_bytes })
+    }
+
+    fn number(option: &str, value: Option<&String>) -> Result<u64, String> {
+        value
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| format!("{} expects a non-negative number", option))
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(2);
+    });
+
+    if let Err(e) = run(&config) {
+        eprintln!("inspector: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(config: &Config) -> Result<(), Box<dyn Error>> {
+    let print: Box<dyn Fn()> = match &config.target {
+        Target::RingBuffer(file) => {
+            let buffer = UnsafeBuffer::map_existing_file(file).map_err(|e| format!("{}: {}", file, e))?;
+            let ring_buffer = OneToOneRingBuffer::try_new(buffer).map_err(|e| format!("{}: {}", file, e))?;
+            let (file, max_records, max_bytes) = (file.clone(), config.max_records, config.max_bytes);
+            Box::new(move || print_ring_buffer(&file, &ring_buffer, max_records, max_bytes))
+        }
+        Target::Counters(meta_data_file, values_file) => {
+            let meta_data = UnsafeBuffer::map_existing_file(meta_data_file).map_err(|e| format!("{}: {}", meta_data_file, e))?;
+            let values = UnsafeBuffer::map_existing_file(values_file).map_err(|e| format!("{}: {}", values_file, e))?;
+            if meta_data.capacity() < (values.capacity() / COUNTER_LENGTH) * METADATA_LENGTH {
+                return Err(format!("{} is too small for the counters in {}", meta_data_file, values_file).into());
+            }
+            let reader = CountersReader::new(meta_data, values);
+            Box::new(move || print_counters(&reader))
+        }
+    };
+
+    match config.watch {
+        None => print(),
+        Some(interval) => loop {
+            // clear the screen and move the cursor home before each refresh
+            print!("\x1b[2J\x1b[H");
+            print();
+            thread::sleep(interval);
+        },
+    }
+
+    Ok(())
+}
+
+fn print_ring_buffer(file: &str, ring_buffer: &OneToOneRingBuffer, max_records: usize, max_bytes: usize) {
+    let buffer = ring_buffer.buffer();
+    let capacity = ring_buffer.capacity();
+    let producer_position = ring_buffer.producer_position();
+    let consumer_position = ring_buffer.consumer_position();
+    let heartbeat_time = ring_buffer.consumer_heartbeat_time();
+    // read rather than next_correlation_id, which would increment it
+    let correlation_counter = buffer.get_long_volatile(capacity + CORRELATION_COUNTER_OFFSET);
+
+    println!("file:                {}", file);
+    println!("capacity:            {}", capacity);
+    println!("max msg length:      {}", ring_buffer.max_msg_length());
+    println!("producer position:   {}", producer_position);
+    println!("consumer position:   {}", consumer_position);
+    println!("size:                {}", ring_buffer.size());
+    println!("heartbeat time:      {}{}", heartbeat_time, heartbeat_age(heartbeat_time));
+    println!("correlation counter: {}", correlation_counter);
+    println!();
+
+    let mask = capacity as i64 - 1;
+    let mut position = consumer_position;
+    let mut count = 0;
+    while position < producer_position && count < max_records {
+        let index = (position & mask) as i32;
+        let record_length = buffer.get_int_volatile(length_offset(index));
+        if !is_valid_record(index, record_length, capacity) {
+            println!("{:>10} corrupt record, length={}", index, record_length);
+            return;
+        }
+        if record_length <= 0 {
+            // the producer has claimed the space but not yet committed the record
+            println!("{:>10} in progress, length={}", index, (-record_length - HEADER_LENGTH).max(0));
+            return;
+        }
+
+        let msg_type_id = buffer.get_int(type_offset(index));
+        let length = record_length - HEADER_LENGTH;
+        if msg_type_id == PADDING_MSG_TYPE_ID {
+            println!("{:>10} padding length={}", index, length);
+        } else {
+            let printed = (length as usize).min(max_bytes).min((capacity - encoded_msg_offset(index)) as usize);
+            let bytes = unsafe {
+                let ptr = buffer.byte_array().add((buffer.address_offset() + encoded_msg_offset(index)) as usize);
+                slice::from_raw_parts(ptr, printed)
+            };
+            let ellipsis = if printed < length as usize { "..." } else { "" };
+            println!("{:>10} type={} length={} {}{}", index, msg_type_id, length, bit_util::to_hex0(bytes), ellipsis);
+        }
+
+        position += bit_util::align(record_length, ALIGNMENT) as i64;
+        count += 1;
+    }
+
+    if position < producer_position {
+        println!("... {} more bytes", producer_position - position);
+    }
+}
+
+// the record header comes straight from the file, so check it before reading the record. A
+// negative length is a claim in progress and 0 a claim whose header is not yet written.
+fn is_valid_record(index: i32, record_length: i32, capacity: i32) -> bool {
+    let length = record_length.unsigned_abs() as i64;
+    index % ALIGNMENT == 0 && (record_length == 0 || (length >= HEADER_LENGTH as i64 && index as i64 + length <= capacity as i64))
+}
+
+fn heartbeat_age(heartbeat_time: i64) -> String {
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_millis() as i64).unwrap_or(0);
+    if heartbeat_time <= 0 || heartbeat_time > now_ms {
+        return String::new();
+    }
+    format!(" ({} ms ago)", now_ms - heartbeat_time)
+}
+
+fn print_counters(reader: &CountersReader) {
+    println!("{:>6} {:>6} {:>20}  label", "id", "type", "value");
+    reader.for_each(|counter_id, type_id, _key, label| {
+        if let Ok(value) = reader.get_counter_value(counter_id) {
+            println!("{:>6} {:>6} {:>20}  {}", counter_id, type_id, value, label);
+        }
+    });
+}
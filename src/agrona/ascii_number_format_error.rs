@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors from parsing or encoding ASCII numbers in a buffer, Agrona's `AsciiNumberFormatException`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiNumberFormatError {
+    Empty { index: i32, length: i32 },
+    InvalidDigit { index: i32, value: u8 },
+    Overflow { index: i32, length: i32 },
+    NegativeValue(i64),
+    ValueTooLong { value: i64, length: i32 },
+}
+
+impl fmt::Display for AsciiNumberFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiNumberFormatError::Empty { index, length } =>
+                write!(f, "empty string: index={} length={}", index, length),
+            AsciiNumberFormatError::InvalidDigit { index, value } =>
+                write!(f, "'{}' is not a valid digit @ {}", value.escape_ascii(), index),
+            AsciiNumberFormatError::Overflow { index, length } =>
+                write!(f, "overflow parsing: index={} length={}", index, length),
+            AsciiNumberFormatError::NegativeValue(value) =>
+                write!(f, "natural number must not be negative: {}", value),
+            AsciiNumberFormatError::ValueTooLong { value, length } =>
+                write!(f, "value: {} will not fit in {} digits", value, length),
+        }
+    }
+}
+
+impl Error for AsciiNumberFormatError {}
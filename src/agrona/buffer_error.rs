@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt;
+use std::str::Utf8Error;
+
+/// Errors from accessing a buffer outside of its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferError {
+    IndexOutOfBounds { index: i32, length: i32, capacity: i32 },
+    LimitBeyondCapacity { limit: i32, capacity: i32 },
+    InvalidOffset(i32),
+    InvalidLength(i32),
+    InvalidWrap { offset: i32, length: i32, capacity: i32 },
+    EncodedLengthTooLong { length: usize, max_length: i32 },
+    InvalidUtf8 { index: i32, error: Utf8Error },
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BufferError::IndexOutOfBounds { index, length, capacity } =>
+                write!(f, "index={} length={} capacity={}", index, length, capacity),
+            BufferError::LimitBeyondCapacity { limit, capacity } =>
+                write!(f, "limit={} is beyond capacity={}", limit, capacity),
+            BufferError::InvalidOffset(offset) => write!(f, "invalid offset={}", offset),
+            BufferError::InvalidLength(length) => write!(f, "invalid length={}", length),
+            BufferError::InvalidWrap { offset, length, capacity } =>
+                write!(f, "offset={} length={} not valid for capacity={}", offset, length, capacity),
+            BufferError::EncodedLengthTooLong { length, max_length } =>
+                write!(f, "encoded string larger than maximum size: length={} max={}", length, max_length),
+            BufferError::InvalidUtf8 { index, error } => write!(f, "invalid UTF-8 string at index={}: {}", index, error),
+        }
+    }
+}
+
+impl Error for BufferError {}
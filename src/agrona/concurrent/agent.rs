@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+
+/// An agent is scheduled to do work on a thread on a duty cycle, see `AgentRunner` and
+/// `AgentInvoker`. Each agent should have a defined role in a system.
+pub trait Agent {
+    /// Called by the agent runner on its thread before the first `do_work`. An error here skips
+    /// the duty cycle and goes straight to `on_close`.
+    fn on_start(&mut self) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    /// An agent should implement this method to do its work, returning the amount of work done so
+    /// the idle strategy can back off when there is none. Return `AgentError::Terminate` to stop
+    /// the agent, any other error is passed to the error handler and the duty cycle continues.
+    fn do_work(&mut self) -> Result<i32, AgentError>;
+
+    /// Called by the agent runner on its thread after the last `do_work`.
+    fn on_close(&mut self) {}
+
+    fn role_name(&self) -> &str;
+}
+
+impl<A: Agent + ?Sized> Agent for Box<A> {
+    fn on_start(&mut self) -> Result<(), AgentError> {
+        (**self).on_start()
+    }
+
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        (**self).do_work()
+    }
+
+    fn on_close(&mut self) {
+        (**self).on_close()
+    }
+
+    fn role_name(&self) -> &str {
+        (**self).role_name()
+    }
+}
+
+/// Failure of an agent's duty cycle. Any `Error` converts into `AgentError::Failed`, so `?` can be
+/// used in `do_work`.
+#[derive(Debug)]
+pub enum AgentError {
+    /// Ask the runner to stop the agent, like Agrona's `AgentTerminationException`.
+    Terminate,
+    Failed(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentError::Terminate => write!(f, "agent terminated"),
+            AgentError::Failed(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl<E> From<E> for AgentError where E: Error + Send + Sync + 'static {
+    fn from(error: E) -> Self {
+        AgentError::Failed(Box::new(error))
+    }
+}
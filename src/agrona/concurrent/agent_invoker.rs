@@ -0,0 +1,91 @@
+use crate::agrona::concurrent::agent::{Agent, AgentError};
+use crate::agrona::error_handler::ErrorHandler;
+
+/// Drive an `Agent` from the caller's thread, e.g. in tests or when sharing a thread with other
+/// work. `invoke` runs a single duty cycle.
+pub struct AgentInvoker<A: Agent, H: ErrorHandler> {
+    agent: A,
+    error_handler: H,
+    is_started: bool,
+    is_running: bool,
+    is_closed: bool,
+}
+
+impl<A: Agent, H: ErrorHandler> AgentInvoker<A, H> {
+    pub fn new(error_handler: H, agent: A) -> Self {
+        AgentInvoker {
+            agent,
+            error_handler,
+            is_started: false,
+            is_running: false,
+            is_closed: false,
+        }
+    }
+
+    pub fn agent(&self) -> &A {
+        &self.agent
+    }
+
+    pub fn agent_mut(&mut self) -> &mut A {
+        &mut self.agent
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.is_started
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.is_closed
+    }
+
+    /// Call `on_start` on the agent, once only. The agent is closed if it fails to start.
+    pub fn start(&mut self) {
+        if self.is_started {
+            return;
+        }
+        self.is_started = true;
+        match self.agent.on_start() {
+            Ok(()) => self.is_running = true,
+            Err(e) => {
+                if !matches!(e, AgentError::Terminate) {
+                    self.error_handler.on_error(&e);
+                }
+                self.close();
+            }
+        }
+    }
+
+    /// Run one duty cycle of the agent if it is running.
+    ///
+    /// @return the work count of the cycle, or 0 if the agent is not running or failed.
+    pub fn invoke(&mut self) -> i32 {
+        if !self.is_running {
+            return 0;
+        }
+        match self.agent.do_work() {
+            Ok(work_count) => work_count,
+            Err(AgentError::Terminate) => {
+                self.close();
+                0
+            }
+            Err(e) => {
+                self.error_handler.on_error(&e);
+                0
+            }
+        }
+    }
+
+    /// Call `on_close` on the agent, once only.
+    pub fn close(&mut self) {
+        if self.is_closed {
+            return;
+        }
+        self.is_running = false;
+        self.is_closed = true;
+        self.agent.on_close();
+    }
+}
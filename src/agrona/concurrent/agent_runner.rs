@@ -0,0 +1,123 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use crate::agrona::concurrent::agent::{Agent, AgentError};
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+use crate::agrona::concurrent::thread_affinity::ThreadAffinity;
+use crate::agrona::error_handler::ErrorHandler;
+
+/// Runs an `Agent` on a dedicated thread, idling with an `IdleStrategy` between duty cycles that
+/// do no work and passing errors to an `ErrorHandler`.
+///
+/// The agent is stopped by `close`, or when the runner is dropped. A panic in the agent stops it
+/// and is returned by `close`.
+pub struct AgentRunner {
+    role_name: String,
+    is_running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AgentRunner {
+    /// Start the agent on a new thread named after its role.
+    pub fn start_on_thread<A, I, H>(agent: A, idle_strategy: I, error_handler: H) -> io::Result<Self>
+    where A: Agent + Send + 'static, I: IdleStrategy + Send + 'static, H: ErrorHandler + Send + 'static {
+        Self::spawn(agent, idle_strategy, error_handler, None)
+    }
+
+    /// Start the agent on a new thread which first applies `affinity` to itself. Failing to apply
+    /// it is returned as an error and the agent is not started.
+    pub fn start_on_thread_with_affinity<A, I, H>(agent: A, idle_strategy: I, error_handler: H, affinity: ThreadAffinity) -> io::Result<Self>
+    where A: Agent + Send + 'static, I: IdleStrategy + Send + 'static, H: ErrorHandler + Send + 'static {
+        Self::spawn(agent, idle_strategy, error_handler, Some(affinity))
+    }
+
+    fn spawn<A, I, H>(agent: A, idle_strategy: I, error_handler: H, affinity: Option<ThreadAffinity>) -> io::Result<Self>
+    where A: Agent + Send + 'static, I: IdleStrategy + Send + 'static, H: ErrorHandler + Send + 'static {
+        let role_name = agent.role_name().to_string();
+        let is_running = Arc::new(AtomicBool::new(true));
+        let running = Arc::clone(&is_running);
+        let (applied_sender, applied) = mpsc::channel();
+        let thread = thread::Builder::new()
+            .name(role_name.clone())
+            .spawn(move || {
+                let result = affinity.map_or(Ok(()), |affinity| affinity.apply());
+                let is_applied = result.is_ok();
+                let _ = applied_sender.send(result);
+                if is_applied {
+                    Self::run(agent, idle_strategy, error_handler, &running);
+                }
+            })?;
+
+        if let Err(e) = applied.recv().unwrap_or(Ok(())) {
+            let _ = thread.join();
+            return Err(e);
+        }
+
+        Ok(AgentRunner {
+            role_name,
+            is_running,
+            thread: Some(thread),
+        })
+    }
+
+    fn run<A: Agent, I: IdleStrategy, H: ErrorHandler>(mut agent: A, mut idle_strategy: I, mut error_handler: H, is_running: &AtomicBool) {
+        let _stopped = Stopped(is_running);
+        match agent.on_start() {
+            Ok(()) => {
+                while is_running.load(Ordering::Acquire) {
+                    match agent.do_work() {
+                        Ok(work_count) => idle_strategy.idle(work_count),
+                        Err(AgentError::Terminate) => break,
+                        Err(e) => error_handler.on_error(&e),
+                    }
+                }
+            }
+            Err(AgentError::Terminate) => {}
+            Err(e) => error_handler.on_error(&e),
+        }
+        agent.on_close();
+    }
+
+    pub fn role_name(&self) -> &str {
+        &self.role_name
+    }
+
+    /// Has the agent not yet stopped, either by `close` or by terminating itself.
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::Acquire)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.thread.is_none()
+    }
+
+    /// Stop the agent and wait for its thread to finish `on_close`.
+    ///
+    /// @return the panic payload if the agent panicked, which is only returned by the first call.
+    pub fn close(&mut self) -> thread::Result<()> {
+        self.is_running.store(false, Ordering::Release);
+        match self.thread.take() {
+            Some(thread) => {
+                thread.thread().unpark();
+                thread.join()
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for AgentRunner {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// Clears the running flag when the agent's thread finishes, including by unwinding from a panic.
+struct Stopped<'a>(&'a AtomicBool);
+
+impl Drop for Stopped<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
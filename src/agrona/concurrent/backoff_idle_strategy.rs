@@ -0,0 +1,100 @@
+use std::{hint, thread};
+use std::time::Duration;
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+
+pub const ALIAS: &str = "backoff";
+
+pub const DEFAULT_MAX_SPINS: u64 = 10;
+pub const DEFAULT_MAX_YIELDS: u64 = 5;
+pub const DEFAULT_MIN_PARK_PERIOD_NS: u64 = 1000;
+pub const DEFAULT_MAX_PARK_PERIOD_NS: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    NotIdle,
+    Spinning,
+    Yielding,
+    Parking,
+}
+
+/// Idling that backs off progressively: spin, then yield, then park for an exponentially
+/// increasing period up to `max_park_period_ns`.
+#[derive(Debug, Clone)]
+pub struct BackoffIdleStrategy {
+    max_spins: u64,
+    max_yields: u64,
+    min_park_period_ns: u64,
+    max_park_period_ns: u64,
+    state: State,
+    spins: u64,
+    yields: u64,
+    park_period_ns: u64,
+}
+
+impl BackoffIdleStrategy {
+    /// @param max_spins          to perform before moving to `thread::yield_now`.
+    /// @param max_yields         to perform before moving to `thread::park_timeout`.
+    /// @param min_park_period_ns to use when initiating parking.
+    /// @param max_park_period_ns to use for end duration when parking.
+    pub fn new(max_spins: u64, max_yields: u64, min_park_period_ns: u64, max_park_period_ns: u64) -> Self {
+        BackoffIdleStrategy {
+            max_spins,
+            max_yields,
+            min_park_period_ns,
+            max_park_period_ns,
+            state: State::NotIdle,
+            spins: 0,
+            yields: 0,
+            park_period_ns: min_park_period_ns,
+        }
+    }
+}
+
+impl Default for BackoffIdleStrategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SPINS, DEFAULT_MAX_YIELDS, DEFAULT_MIN_PARK_PERIOD_NS, DEFAULT_MAX_PARK_PERIOD_NS)
+    }
+}
+
+impl IdleStrategy for BackoffIdleStrategy {
+    fn idle0(&mut self) {
+        match self.state {
+            State::NotIdle => {
+                self.state = State::Spinning;
+                self.spins += 1;
+            }
+            State::Spinning => {
+                hint::spin_loop();
+                self.spins += 1;
+                if self.spins > self.max_spins {
+                    self.state = State::Yielding;
+                    self.yields = 0;
+                }
+            }
+            State::Yielding => {
+                self.yields += 1;
+                if self.yields > self.max_yields {
+                    self.state = State::Parking;
+                    self.park_period_ns = self.min_park_period_ns;
+                } else {
+                    thread::yield_now();
+                }
+            }
+            State::Parking => {
+                thread::park_timeout(Duration::from_nanos(self.park_period_ns));
+                self.park_period_ns = (self.park_period_ns << 1).min(self.max_park_period_ns);
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.spins = 0;
+        self.yields = 0;
+        self.park_period_ns = self.min_park_period_ns;
+        self.state = State::NotIdle;
+    }
+
+    fn alias(&self) -> &'static str {
+        ALIAS
+    }
+}
@@ -0,0 +1,19 @@
+use crate::bit_util::{is_power_of_two_i32, CACHE_LINE_LENGTH, SIZE_OF_LONG};
+
+/// Offset within the trailer for where the tail intended value is stored.
+pub const TAIL_INTENT_COUNTER_OFFSET: i32 = 0;
+
+/// Offset within the trailer for where the tail value is stored.
+pub const TAIL_COUNTER_OFFSET: i32 = TAIL_INTENT_COUNTER_OFFSET + SIZE_OF_LONG;
+
+/// Offset within the trailer for where the latest sequence value is stored.
+pub const LATEST_COUNTER_OFFSET: i32 = TAIL_COUNTER_OFFSET + SIZE_OF_LONG;
+
+/// Total size of the trailer.
+pub const TRAILER_LENGTH: i32 = CACHE_LINE_LENGTH * 2;
+
+pub fn check_capacity(capacity: i32) {
+    if !is_power_of_two_i32(capacity) {
+        panic!("capacity must be a positive power of 2 + TRAILER_LENGTH: capacity={}", capacity);
+    }
+}
@@ -0,0 +1,31 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a message could not be transmitted, or handed over by a `CopyBroadcastReceiver`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BroadcastError {
+    /// The transmitter overwrote messages before they were received. The receiver has moved on
+    /// to the latest message, so receiving again carries on from there.
+    Lapped,
+    ScratchTooSmall { length: i32, capacity: i32 },
+    InvalidTypeId(i32),
+    InvalidMessageLength(i32),
+    MessageTooLong { length: i32, max_msg_length: i32 },
+}
+
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BroadcastError::Lapped => write!(f, "unable to keep up with broadcast"),
+            BroadcastError::ScratchTooSmall { length, capacity } =>
+                write!(f, "buffer required length of {} but only has {}", length, capacity),
+            BroadcastError::InvalidTypeId(msg_type_id) =>
+                write!(f, "type id must be greater than zero, msgTypeId={}", msg_type_id),
+            BroadcastError::InvalidMessageLength(length) => write!(f, "invalid message length={}", length),
+            BroadcastError::MessageTooLong { length, max_msg_length } =>
+                write!(f, "encoded message exceeds maxMsgLength of {}, length={}", max_msg_length, length),
+        }
+    }
+}
+
+impl Error for BroadcastError {}
@@ -0,0 +1,143 @@
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::broadcast::broadcast_buffer_descriptor::{check_capacity, LATEST_COUNTER_OFFSET, TAIL_COUNTER_OFFSET, TAIL_INTENT_COUNTER_OFFSET, TRAILER_LENGTH};
+use crate::agrona::concurrent::broadcast::record_descriptor::{length_offset, msg_offset, type_offset, HEADER_LENGTH, PADDING_MSG_TYPE_ID, RECORD_ALIGNMENT};
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::align;
+use std::sync::Arc;
+use std::sync::atomic::{fence, AtomicI64, Ordering};
+
+/// Receive messages broadcast from a `BroadcastTransmitter` via an underlying buffer.
+///
+/// Receivers can join a transmission stream at any point by consuming the latest message at the
+/// point of joining and forward. If a receiver cannot keep up it is lapped: it skips ahead to the
+/// latest message and the lapped count is incremented.
+pub struct BroadcastReceiver {
+    buffer: UnsafeBuffer,
+    capacity: i32,
+    tail_intent_counter_index: i32,
+    tail_counter_index: i32,
+    latest_counter_index: i32,
+    record_offset: i32,
+    cursor: i64,
+    next_record: i64,
+    lapped_count: AtomicI64,
+    _memory: Option<Arc<UnsafeBuffer>>,
+}
+
+unsafe impl Send for BroadcastReceiver {}
+
+impl BroadcastReceiver {
+    /// Receive from `buffer`, e.g. mapped from a file written by a transmitter in another process.
+    /// Use `BroadcastTransmitter::new_receiver` for a transmitter in the same process.
+    pub fn new(buffer: UnsafeBuffer) -> Self {
+        Self::with_memory(buffer, None)
+    }
+
+    pub(crate) fn shared(buffer: UnsafeBuffer, memory: Arc<UnsafeBuffer>) -> Self {
+        Self::with_memory(buffer, Some(memory))
+    }
+
+    fn with_memory(buffer: UnsafeBuffer, memory: Option<Arc<UnsafeBuffer>>) -> Self {
+        let capacity = buffer.capacity() - TRAILER_LENGTH;
+        check_capacity(capacity);
+        buffer.verify_alignment();
+
+        let tail_intent_counter_index = capacity + TAIL_INTENT_COUNTER_OFFSET;
+        let tail_counter_index = capacity + TAIL_COUNTER_OFFSET;
+        let latest_counter_index = capacity + LATEST_COUNTER_OFFSET;
+        let cursor = buffer.get_long_volatile(latest_counter_index);
+
+        BroadcastReceiver {
+            buffer,
+            capacity,
+            tail_intent_counter_index,
+            tail_counter_index,
+            latest_counter_index,
+            record_offset: cursor as i32 & (capacity - 1),
+            cursor,
+            next_record: cursor,
+            lapped_count: AtomicI64::new(0),
+            _memory: memory,
+        }
+    }
+
+    pub fn capacity(&self) -> i32 {
+        self.capacity
+    }
+
+    /// Get the number of times the transmitter has lapped this receiver around the buffer.
+    pub fn lapped_count(&self) -> i64 {
+        self.lapped_count.load(Ordering::Acquire)
+    }
+
+    /// Type of the message received.
+    pub fn type_id(&self) -> i32 {
+        self.buffer.get_int(type_offset(self.record_offset))
+    }
+
+    /// The offset for the beginning of the next message in the transmission stream.
+    pub fn offset(&self) -> i32 {
+        msg_offset(self.record_offset)
+    }
+
+    /// The length of the next message in the transmission stream.
+    pub fn length(&self) -> i32 {
+        self.buffer.get_int(length_offset(self.record_offset)) - HEADER_LENGTH
+    }
+
+    /// The underlying buffer containing the broadcast message stream.
+    pub fn buffer(&self) -> &UnsafeBuffer {
+        &self.buffer
+    }
+
+    /// Non-blocking receive of next message from the transmission stream.
+    ///
+    /// If loss has occurred then `lapped_count` will be incremented.
+    ///
+    /// @return true if transmission is available with `offset`, `length` and `type_id` set for the next message to be consumed.
+    /// If no transmission is available then false.
+    pub fn receive_next(&mut self) -> bool {
+        let mut is_available = false;
+        let buffer = &self.buffer;
+        let tail = buffer.get_long_volatile(self.tail_counter_index);
+        let mut cursor = self.next_record;
+
+        if tail > cursor {
+            let capacity = self.capacity;
+            let mut record_offset = cursor as i32 & (capacity - 1);
+
+            if !self.validate0(cursor) {
+                self.lapped_count.store(self.lapped_count.load(Ordering::Relaxed) + 1, Ordering::Release);
+                cursor = buffer.get_long(self.latest_counter_index);
+                record_offset = cursor as i32 & (capacity - 1);
+            }
+
+            self.cursor = cursor;
+            self.next_record = cursor + align(buffer.get_int(length_offset(record_offset)), RECORD_ALIGNMENT) as i64;
+
+            if PADDING_MSG_TYPE_ID == buffer.get_int(type_offset(record_offset)) {
+                record_offset = 0;
+                self.cursor = self.next_record;
+                self.next_record += align(buffer.get_int(length_offset(record_offset)), RECORD_ALIGNMENT) as i64;
+            }
+
+            self.record_offset = record_offset;
+            is_available = true;
+        }
+
+        is_available
+    }
+
+    /// Validate that the current received record has not been written over by the transmitter.
+    ///
+    /// @return true if still valid otherwise false.
+    pub fn validate(&self) -> bool {
+        fence(Ordering::Acquire);
+        self.validate0(self.cursor)
+    }
+
+    fn validate0(&self, cursor: i64) -> bool {
+        (cursor + self.capacity as i64) > self.buffer.get_long_volatile(self.tail_intent_counter_index)
+    }
+}
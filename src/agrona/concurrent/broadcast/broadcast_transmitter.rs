@@ -0,0 +1,130 @@
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::broadcast::broadcast_buffer_descriptor::{check_capacity, LATEST_COUNTER_OFFSET, TAIL_COUNTER_OFFSET, TAIL_INTENT_COUNTER_OFFSET, TRAILER_LENGTH};
+use crate::agrona::concurrent::broadcast::broadcast_error::BroadcastError;
+use crate::agrona::concurrent::broadcast::broadcast_receiver::BroadcastReceiver;
+use crate::agrona::concurrent::broadcast::record_descriptor::{calculate_max_message_length, check_type_id, length_offset, msg_offset, type_offset, HEADER_LENGTH, PADDING_MSG_TYPE_ID, RECORD_ALIGNMENT};
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::align;
+use std::sync::Arc;
+use std::sync::atomic::{fence, Ordering};
+
+/// Transmit messages via an underlying broadcast buffer to zero or more `BroadcastReceiver`s.
+///
+/// There is no back-pressure: the transmitter overwrites old records and receivers which fall a
+/// whole buffer behind are lapped.
+pub struct BroadcastTransmitter {
+    // a view of `memory`, which receivers from `new_receiver` share to keep it alive
+    buffer: UnsafeBuffer,
+    memory: Arc<UnsafeBuffer>,
+    capacity: i32,
+    max_msg_length: i32,
+    tail_intent_count_index: i32,
+    tail_counter_index: i32,
+    latest_counter_index: i32,
+}
+
+unsafe impl Send for BroadcastTransmitter {}
+
+impl BroadcastTransmitter {
+    // the Arc is only dropped across threads, never used to access the buffer, see `view`
+    #[allow(clippy::arc_with_non_send_sync)]
+    pub fn new(buffer: UnsafeBuffer) -> Self {
+        let capacity = buffer.capacity() - TRAILER_LENGTH;
+        check_capacity(capacity);
+        buffer.verify_alignment();
+        let memory = Arc::new(buffer);
+
+        BroadcastTransmitter {
+            buffer: view(&memory),
+            memory,
+            capacity,
+            max_msg_length: calculate_max_message_length(capacity),
+            tail_intent_count_index: capacity + TAIL_INTENT_COUNTER_OFFSET,
+            tail_counter_index: capacity + TAIL_COUNTER_OFFSET,
+            latest_counter_index: capacity + LATEST_COUNTER_OFFSET,
+        }
+    }
+
+    pub fn capacity(&self) -> i32 {
+        self.capacity
+    }
+
+    pub fn max_msg_length(&self) -> i32 {
+        self.max_msg_length
+    }
+
+    pub fn buffer(&self) -> &UnsafeBuffer {
+        &self.buffer
+    }
+
+    /// Create a receiver over this transmitter's buffer, joining the stream at the latest message.
+    /// The buffer stays allocated while any such receiver is alive.
+    pub fn new_receiver(&self) -> BroadcastReceiver {
+        BroadcastReceiver::shared(view(&self.memory), Arc::clone(&self.memory))
+    }
+
+    /// Transmit a message to receivers via the broadcast buffer.
+    ///
+    /// @param msgTypeId type of the message to be transmitted.
+    /// @param srcBuffer containing the encoded message to be transmitted.
+    /// @param srcIndex  srcIndex in the source buffer at which the encoded message begins.
+    /// @param length    in bytes of the encoded message.
+    /// @return an error if the msgTypeId is not valid, or if the length is negative or greater than `max_msg_length`.
+    pub fn transmit(&mut self, msg_type_id: i32, src_buffer: &UnsafeBuffer, src_index: i32, length: i32) -> Result<(), BroadcastError> {
+        check_type_id(msg_type_id)?;
+        self.check_message_length(length)?;
+
+        let capacity = self.capacity;
+        let mut current_tail = self.buffer.get_long(self.tail_counter_index);
+        let mut record_offset = current_tail as i32 & (capacity - 1);
+        let record_length = HEADER_LENGTH + length;
+        let aligned_record_length = align(record_length, RECORD_ALIGNMENT);
+        let new_tail = current_tail + aligned_record_length as i64;
+
+        let to_end_of_buffer = capacity - record_offset;
+        if to_end_of_buffer < aligned_record_length {
+            self.signal_tail_intent(new_tail + to_end_of_buffer as i64);
+            self.insert_padding_record(record_offset, to_end_of_buffer);
+
+            current_tail += to_end_of_buffer as i64;
+            record_offset = 0;
+        } else {
+            self.signal_tail_intent(new_tail);
+        }
+
+        let buffer = &mut self.buffer;
+        buffer.put_int(length_offset(record_offset), record_length);
+        buffer.put_int(type_offset(record_offset), msg_type_id);
+
+        buffer.put_bytes2(msg_offset(record_offset), src_buffer, src_index, length);
+
+        buffer.put_long(self.latest_counter_index, current_tail);
+        buffer.put_long_ordered(self.tail_counter_index, current_tail + aligned_record_length as i64);
+        Ok(())
+    }
+
+    fn check_message_length(&self, length: i32) -> Result<(), BroadcastError> {
+        if length < 0 {
+            return Err(BroadcastError::InvalidMessageLength(length));
+        } else if length > self.max_msg_length {
+            return Err(BroadcastError::MessageTooLong { length, max_msg_length: self.max_msg_length });
+        }
+        Ok(())
+    }
+
+    fn signal_tail_intent(&mut self, new_tail: i64) {
+        self.buffer.put_long_ordered(self.tail_intent_count_index, new_tail);
+        fence(Ordering::Release);
+    }
+
+    fn insert_padding_record(&mut self, record_offset: i32, length: i32) {
+        self.buffer.put_int(length_offset(record_offset), length);
+        self.buffer.put_int(type_offset(record_offset), PADDING_MSG_TYPE_ID);
+    }
+}
+
+fn view(memory: &UnsafeBuffer) -> UnsafeBuffer {
+    // SAFETY: the view is only used alongside an Arc of `memory`, which keeps the memory alive
+    unsafe { UnsafeBuffer::wrap(memory.byte_array().add(memory.address_offset() as usize), memory.capacity()) }
+}
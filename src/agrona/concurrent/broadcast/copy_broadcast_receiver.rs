@@ -0,0 +1,65 @@
+use crate::agrona::concurrent::broadcast::broadcast_error::BroadcastError;
+use crate::agrona::concurrent::broadcast::broadcast_receiver::BroadcastReceiver;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+
+/// Receiver that copies each message out of the broadcast buffer into a scratch buffer before
+/// handing it over, so the message cannot be overwritten while it is being handled.
+pub struct CopyBroadcastReceiver {
+    receiver: BroadcastReceiver,
+    scratch_buffer: UnsafeBuffer,
+}
+
+unsafe impl Send for CopyBroadcastReceiver {}
+
+impl CopyBroadcastReceiver {
+    pub fn new(mut receiver: BroadcastReceiver, scratch_buffer: UnsafeBuffer) -> Self {
+        while receiver.receive_next() {
+            // If we're reading then catch up to the latest
+        }
+
+        CopyBroadcastReceiver {
+            receiver,
+            scratch_buffer,
+        }
+    }
+
+    pub fn lapped_count(&self) -> i64 {
+        self.receiver.lapped_count()
+    }
+
+    /// Receive one message from the broadcast buffer.
+    ///
+    /// @param handler to be called for each message received.
+    /// @return the number of messages that have been received, or an error if lapped by the
+    /// transmitter or the scratch buffer is too small.
+    pub fn receive<F>(&mut self, mut func: F) -> Result<i32, BroadcastError> where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        let mut messages_received = 0;
+        let receiver = &mut self.receiver;
+        let last_seen_lapped_count = receiver.lapped_count();
+
+        if receiver.receive_next() {
+            if last_seen_lapped_count != receiver.lapped_count() {
+                return Err(BroadcastError::Lapped);
+            }
+
+            let length = receiver.length();
+            let capacity = self.scratch_buffer.capacity();
+            if length > capacity && !self.scratch_buffer.is_expandable() {
+                return Err(BroadcastError::ScratchTooSmall { length, capacity });
+            }
+
+            let msg_type_id = receiver.type_id();
+            self.scratch_buffer.put_bytes2(0, receiver.buffer(), receiver.offset(), length);
+
+            if !receiver.validate() {
+                return Err(BroadcastError::Lapped);
+            }
+
+            func(msg_type_id, &self.scratch_buffer, 0, length);
+            messages_received = 1;
+        }
+
+        Ok(messages_received)
+    }
+}
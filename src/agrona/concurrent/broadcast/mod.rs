@@ -0,0 +1,6 @@
+pub mod broadcast_buffer_descriptor;
+pub mod broadcast_error;
+pub mod record_descriptor;
+pub mod broadcast_transmitter;
+pub mod broadcast_receiver;
+pub mod copy_broadcast_receiver;
@@ -0,0 +1,40 @@
+use crate::agrona::concurrent::broadcast::broadcast_error::BroadcastError;
+use crate::bit_util::SIZE_OF_INT;
+
+/// Message type is padding to prevent fragmentation in the buffer.
+pub const PADDING_MSG_TYPE_ID: i32 = -1;
+
+/// Offset within the record at which the record length field begins.
+pub const LENGTH_OFFSET: i32 = 0;
+
+/// Offset within the record at which the message type field begins.
+pub const TYPE_OFFSET: i32 = LENGTH_OFFSET + SIZE_OF_INT;
+
+/// Length of the record header in bytes.
+pub const HEADER_LENGTH: i32 = SIZE_OF_INT * 2;
+
+/// Alignment as a multiple of bytes for each record.
+pub const RECORD_ALIGNMENT: i32 = HEADER_LENGTH;
+
+pub fn calculate_max_message_length(capacity: i32) -> i32 {
+    capacity / 8
+}
+
+pub fn length_offset(record_offset: i32) -> i32 {
+    record_offset + LENGTH_OFFSET
+}
+
+pub fn type_offset(record_offset: i32) -> i32 {
+    record_offset + TYPE_OFFSET
+}
+
+pub fn msg_offset(record_offset: i32) -> i32 {
+    record_offset + HEADER_LENGTH
+}
+
+pub fn check_type_id(msg_type_id: i32) -> Result<(), BroadcastError> {
+    if msg_type_id < 1 {
+        return Err(BroadcastError::InvalidTypeId(msg_type_id));
+    }
+    Ok(())
+}
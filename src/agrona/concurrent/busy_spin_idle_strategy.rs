@@ -0,0 +1,20 @@
+use std::hint;
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+
+pub const ALIAS: &str = "spin";
+
+/// Busy spin with a spin-loop hint to the CPU. Lowest latency, at the cost of a whole core.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BusySpinIdleStrategy;
+
+impl IdleStrategy for BusySpinIdleStrategy {
+    fn idle0(&mut self) {
+        hint::spin_loop();
+    }
+
+    fn reset(&mut self) {}
+
+    fn alias(&self) -> &'static str {
+        ALIAS
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use crate::agrona::concurrent::agent::{Agent, AgentError};
+use crate::agrona::concurrent::cached_epoch_clock::CachedEpochClock;
+use crate::agrona::concurrent::cached_nano_clock::CachedNanoClock;
+use crate::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use crate::agrona::concurrent::nano_clock::{NanoClock, SystemNanoClock};
+
+/// Agent which updates a `CachedEpochClock` and a `CachedNanoClock` from the system clocks on
+/// every duty cycle. Run it on its own thread with a sleeping idle strategy giving the resolution,
+/// or in a `CompositeAgent` so the clocks are updated once per cycle of the other agents.
+#[derive(Debug)]
+pub struct CachedClockUpdater {
+    epoch_clock: Arc<CachedEpochClock>,
+    nano_clock: Arc<CachedNanoClock>,
+}
+
+impl CachedClockUpdater {
+    pub fn new(epoch_clock: Arc<CachedEpochClock>, nano_clock: Arc<CachedNanoClock>) -> Self {
+        let updater = CachedClockUpdater { epoch_clock, nano_clock };
+        updater.update();
+        updater
+    }
+
+    fn update(&self) {
+        self.epoch_clock.update(SystemEpochClock.time());
+        self.nano_clock.update(SystemNanoClock.nano_time());
+    }
+}
+
+impl Agent for CachedClockUpdater {
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        self.update();
+        Ok(0)
+    }
+
+    fn role_name(&self) -> &str {
+        "cached-clock-updater"
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use crate::agrona::concurrent::epoch_clock::EpochClock;
+
+/// An `EpochClock` that caches a timestamp which can be updated with `update`, e.g. once per duty
+/// cycle by a `CachedClockUpdater`, so readers avoid the cost of reading the system clock.
+#[derive(Debug, Default)]
+pub struct CachedEpochClock {
+    time_ms: AtomicI64,
+}
+
+impl CachedEpochClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the cached time with a new value in milliseconds since epoch.
+    pub fn update(&self, time_ms: i64) {
+        self.time_ms.store(time_ms, Ordering::Release);
+    }
+
+    /// Advance the cached time by `delta_ms`.
+    pub fn advance(&self, delta_ms: i64) {
+        self.time_ms.fetch_add(delta_ms, Ordering::AcqRel);
+    }
+}
+
+impl EpochClock for CachedEpochClock {
+    fn time(&self) -> i64 {
+        self.time_ms.load(Ordering::Acquire)
+    }
+}
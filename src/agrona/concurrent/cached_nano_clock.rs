@@ -0,0 +1,31 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use crate::agrona::concurrent::nano_clock::NanoClock;
+
+/// A `NanoClock` that caches a timestamp which can be updated with `update`, see
+/// `CachedEpochClock`.
+#[derive(Debug, Default)]
+pub struct CachedNanoClock {
+    nano_time: AtomicI64,
+}
+
+impl CachedNanoClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the cached time with a new value in nanoseconds.
+    pub fn update(&self, nano_time: i64) {
+        self.nano_time.store(nano_time, Ordering::Release);
+    }
+
+    /// Advance the cached time by `delta_ns`.
+    pub fn advance(&self, delta_ns: i64) {
+        self.nano_time.fetch_add(delta_ns, Ordering::AcqRel);
+    }
+}
+
+impl NanoClock for CachedNanoClock {
+    fn nano_time(&self) -> i64 {
+        self.nano_time.load(Ordering::Acquire)
+    }
+}
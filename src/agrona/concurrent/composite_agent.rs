@@ -0,0 +1,59 @@
+use crate::agrona::concurrent::agent::{Agent, AgentError};
+
+/// Group several agents so they can share one thread. Work is done by each agent in turn and the
+/// work counts are summed.
+pub struct CompositeAgent {
+    agents: Vec<Box<dyn Agent + Send>>,
+    role_name: String,
+    agent_index: usize,
+}
+
+impl CompositeAgent {
+    pub fn new(agents: Vec<Box<dyn Agent + Send>>) -> Self {
+        if agents.is_empty() {
+            panic!("requires at least one sub-agent");
+        }
+        let role_names: Vec<&str> = agents.iter().map(|agent| agent.role_name()).collect();
+        let role_name = format!("[{}]", role_names.join(","));
+
+        CompositeAgent {
+            agents,
+            role_name,
+            agent_index: 0,
+        }
+    }
+}
+
+impl Agent for CompositeAgent {
+    fn on_start(&mut self) -> Result<(), AgentError> {
+        let mut first_error = None;
+        for agent in &mut self.agents {
+            if let Err(e) = agent.on_start() {
+                first_error.get_or_insert(e);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Agents after one that fails are resumed from on the next duty cycle.
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        let mut work_count = 0;
+        while self.agent_index < self.agents.len() {
+            let agent = &mut self.agents[self.agent_index];
+            self.agent_index += 1;
+            work_count += agent.do_work()?;
+        }
+        self.agent_index = 0;
+        Ok(work_count)
+    }
+
+    fn on_close(&mut self) {
+        for agent in &mut self.agents {
+            agent.on_close();
+        }
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role_name
+    }
+}
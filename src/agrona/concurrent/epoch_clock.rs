@@ -0,0 +1,29 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Retrieves the number of milliseconds since 1 Jan 1970 UTC.
+pub trait EpochClock {
+    fn time(&self) -> i64;
+}
+
+/// `EpochClock` reading the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemEpochClock;
+
+impl EpochClock for SystemEpochClock {
+    fn time(&self) -> i64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+    }
+}
+
+impl<F: Fn() -> i64> EpochClock for F {
+    fn time(&self) -> i64 {
+        self()
+    }
+}
+
+impl<C: EpochClock + ?Sized> EpochClock for Arc<C> {
+    fn time(&self) -> i64 {
+        (**self).time()
+    }
+}
@@ -0,0 +1,30 @@
+/// Idle strategy for use by threads when they do not have work to do.
+///
+/// Pass the work count of the last duty cycle, e.g. the messages returned by `RingBuffer::read0`,
+/// to `idle` so the strategy backs off only while there is nothing to do:
+///
+/// ```ignore
+/// loop {
+///     let work_count = ring_buffer.read0(handler, 100);
+///     idle_strategy.idle(work_count);
+/// }
+/// ```
+pub trait IdleStrategy {
+    /// Perform the current idle action, or reset the strategy if `work_count` is positive.
+    fn idle(&mut self, work_count: i32) {
+        if work_count > 0 {
+            self.reset();
+        } else {
+            self.idle0();
+        }
+    }
+
+    /// Perform the current idle action unconditionally, for callers managing their own back-off.
+    fn idle0(&mut self);
+
+    /// Reset the internal state in preparation for entering an idle state again.
+    fn reset(&mut self);
+
+    /// Simple name by which the strategy can be identified, e.g. in configuration.
+    fn alias(&self) -> &'static str;
+}
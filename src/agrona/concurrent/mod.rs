@@ -1,4 +1,23 @@
 pub mod ringbuffer;
 mod controlled_message_handler;
-mod atomic_buffer;
+pub mod atomic_buffer;
 pub mod unsafe_buffer;
+pub mod epoch_clock;
+pub mod nano_clock;
+pub mod cached_epoch_clock;
+pub mod cached_nano_clock;
+pub mod cached_clock_updater;
+pub mod test_clock;
+pub mod broadcast;
+pub mod idle_strategy;
+pub mod busy_spin_idle_strategy;
+pub mod no_op_idle_strategy;
+pub mod yielding_idle_strategy;
+pub mod sleeping_idle_strategy;
+pub mod backoff_idle_strategy;
+pub mod agent;
+pub mod agent_runner;
+pub mod composite_agent;
+pub mod agent_invoker;
+pub mod thread_affinity;
+pub mod status;
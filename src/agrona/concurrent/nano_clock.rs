@@ -0,0 +1,33 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+/// Clock that provides the number of nanoseconds since an arbitrary fixed point, for measuring
+/// elapsed time.
+pub trait NanoClock {
+    fn nano_time(&self) -> i64;
+}
+
+/// `NanoClock` reading the monotonic system clock. The origin is fixed the first time it is read
+/// in the process, so times can be compared between threads but not between processes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemNanoClock;
+
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+impl NanoClock for SystemNanoClock {
+    fn nano_time(&self) -> i64 {
+        ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as i64
+    }
+}
+
+impl<F: Fn() -> i64> NanoClock for F {
+    fn nano_time(&self) -> i64 {
+        self()
+    }
+}
+
+impl<C: NanoClock + ?Sized> NanoClock for Arc<C> {
+    fn nano_time(&self) -> i64 {
+        (**self).nano_time()
+    }
+}
@@ -0,0 +1,17 @@
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+
+pub const ALIAS: &str = "noop";
+
+/// Does nothing at all when idle, the poll loop spins as fast as it can.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoOpIdleStrategy;
+
+impl IdleStrategy for NoOpIdleStrategy {
+    fn idle0(&mut self) {}
+
+    fn reset(&mut self) {}
+
+    fn alias(&self) -> &'static str {
+        ALIAS
+    }
+}
@@ -0,0 +1,209 @@
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::sync::atomic::{fence, AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use futures_core::Stream;
+use crate::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use crate::agrona::concurrent::ringbuffer::ring_buffer::{Claim, RingBuffer};
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+
+/// A parked task waiting on the other side of the ring buffer.
+///
+/// Futex-style: the waiter registers its waker and then re-checks the ring buffer, the other side
+/// only takes the lock to wake it when `parked` is set, so neither side pays for the wakeup while
+/// messages are flowing.
+#[derive(Default)]
+struct Wakeup {
+    parked: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Wakeup {
+    fn park(&self, waker: &Waker) {
+        let mut slot = self.waker.lock().unwrap();
+        if !slot.as_ref().is_some_and(|parked| parked.will_wake(waker)) {
+            *slot = Some(waker.clone());
+        }
+        self.parked.store(true, Ordering::SeqCst);
+        fence(Ordering::SeqCst);
+    }
+
+    fn unpark(&self) {
+        fence(Ordering::SeqCst);
+        if self.parked.swap(false, Ordering::SeqCst) {
+            if let Some(waker) = self.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Wakeups {
+    producer: Wakeup,
+    consumer: Wakeup,
+    producer_dropped: AtomicBool,
+}
+
+/// The producing half of a `OneToOneRingBuffer` split for use from async tasks.
+///
+/// `write` and `claim` wait for free capacity rather than failing with
+/// `RingBufferError::InsufficientCapacity`.
+pub struct AsyncProducer {
+    ring_buffer: Arc<OneToOneRingBuffer>,
+    wakeups: Arc<Wakeups>,
+}
+
+/// The consuming half of a `OneToOneRingBuffer` split for use from async tasks, see `AsyncProducer`.
+///
+/// As a `Stream` it yields each message as its type id and a copy of its bytes, allocating a `Vec`
+/// per message, while `read` hands messages to a handler in place without allocating. Both finish
+/// once the `AsyncProducer` has been dropped and the remaining messages read.
+pub struct AsyncConsumer {
+    ring_buffer: Arc<OneToOneRingBuffer>,
+    wakeups: Arc<Wakeups>,
+}
+
+impl OneToOneRingBuffer {
+    pub fn split_async(self) -> (AsyncProducer, AsyncConsumer) {
+        let ring_buffer = Arc::new(self);
+        let wakeups = Arc::new(Wakeups::default());
+        let producer = AsyncProducer { ring_buffer: Arc::clone(&ring_buffer), wakeups: Arc::clone(&wakeups) };
+        let consumer = AsyncConsumer { ring_buffer, wakeups };
+        (producer, consumer)
+    }
+}
+
+impl AsyncProducer {
+    pub fn capacity(&self) -> i32 {
+        self.ring_buffer.capacity()
+    }
+
+    pub fn max_msg_length(&self) -> i32 {
+        self.ring_buffer.max_msg_length()
+    }
+
+    pub async fn write(&mut self, msg_type_id: i32, src: &[u8]) -> Result<(), RingBufferError> {
+        poll_fn(|cx| {
+            self.poll_capacity(cx, || self.ring_buffer.try_write_vectored(msg_type_id, &[src]))
+        }).await?;
+        self.wakeups.consumer.unpark();
+        Ok(())
+    }
+
+    /// Claim `length` bytes for a message once there is capacity. The consumer is woken when the
+    /// claim is committed or aborted.
+    pub async fn claim(&mut self, msg_type_id: i32, length: i32) -> Result<AsyncClaim<'_>, RingBufferError> {
+        let ring_buffer = &self.ring_buffer;
+        let claim = poll_fn(|cx| self.poll_capacity(cx, || ring_buffer.claim(msg_type_id, length))).await?;
+        Ok(AsyncClaim { claim: Some(claim), consumer: &self.wakeups.consumer })
+    }
+
+    fn poll_capacity<T, F>(&self, cx: &mut Context<'_>, mut attempt: F) -> Poll<Result<T, RingBufferError>>
+    where F: FnMut() -> Result<T, RingBufferError> {
+        match attempt() {
+            Err(RingBufferError::InsufficientCapacity) => {}
+            result => return Poll::Ready(result),
+        }
+        self.wakeups.producer.park(cx.waker());
+        match attempt() {
+            Err(RingBufferError::InsufficientCapacity) => Poll::Pending,
+            result => Poll::Ready(result),
+        }
+    }
+}
+
+impl Drop for AsyncProducer {
+    fn drop(&mut self) {
+        self.wakeups.producer_dropped.store(true, Ordering::Release);
+        self.wakeups.consumer.unpark();
+    }
+}
+
+/// A `Claim` made through an `AsyncProducer`, which wakes the consumer once released.
+pub struct AsyncClaim<'a> {
+    claim: Option<Claim<'a, OneToOneRingBuffer>>,
+    consumer: &'a Wakeup,
+}
+
+impl AsyncClaim<'_> {
+    pub fn index(&self) -> i32 {
+        self.claim.as_ref().unwrap().index()
+    }
+
+    pub fn commit(mut self) -> Result<(), RingBufferError> {
+        self.claim.take().unwrap().commit()
+    }
+
+    pub fn abort(mut self) -> Result<(), RingBufferError> {
+        self.claim.take().unwrap().abort()
+    }
+}
+
+impl std::ops::Deref for AsyncClaim<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.claim.as_ref().unwrap()
+    }
+}
+
+impl std::ops::DerefMut for AsyncClaim<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.claim.as_mut().unwrap()
+    }
+}
+
+impl Drop for AsyncClaim<'_> {
+    fn drop(&mut self) {
+        drop(self.claim.take());
+        self.consumer.unpark();
+    }
+}
+
+impl AsyncConsumer {
+    pub fn capacity(&self) -> i32 {
+        self.ring_buffer.capacity()
+    }
+
+    /// Wait for at least one message and read what is available, as `RingBuffer::read` does.
+    /// Returns 0 once the producer has been dropped and every message read.
+    pub async fn read<F>(&mut self, mut func: F) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        poll_fn(|cx| self.poll_read(cx, |ring_buffer| ring_buffer.read(&mut func))).await
+    }
+
+    fn poll_read<F>(&self, cx: &mut Context<'_>, mut read: F) -> Poll<i32> where F: FnMut(&OneToOneRingBuffer) -> i32 {
+        let consumer_position = self.ring_buffer.consumer_position();
+        let mut messages_read = read(&self.ring_buffer);
+        if messages_read == 0 {
+            self.wakeups.consumer.park(cx.waker());
+            messages_read = read(&self.ring_buffer);
+        }
+        // checked after parking so a producer dropped since is either seen here or wakes us, and
+        // after seeing it dropped one more read picks up everything it wrote
+        if messages_read == 0 && self.wakeups.producer_dropped.load(Ordering::Acquire) {
+            messages_read = read(&self.ring_buffer);
+            if messages_read == 0 {
+                return Poll::Ready(0);
+            }
+        }
+        // reading only padding frees space too
+        if self.ring_buffer.consumer_position() != consumer_position {
+            self.wakeups.producer.unpark();
+        }
+        if messages_read == 0 { Poll::Pending } else { Poll::Ready(messages_read) }
+    }
+}
+
+impl Stream for AsyncConsumer {
+    type Item = (i32, Vec<u8>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut message = None;
+        self.poll_read(cx, |ring_buffer| {
+            ring_buffer.read_messages(|next| message = Some((next.type_id(), next.as_bytes().to_vec())), 1)
+        }).map(|_| message)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::ringbuffer::message::Message;
+use crate::agrona::concurrent::ringbuffer::record_descriptor::{length_offset, type_offset, ALIGNMENT, HEADER_LENGTH};
+use crate::agrona::concurrent::ringbuffer::ring_buffer::PADDING_MSG_TYPE_ID;
+use crate::agrona::concurrent::ringbuffer::ring_buffer_stats::RingBufferStats;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::align;
+
+/// Messages available to the consumer of a ring buffer, returned by `RingBuffer::drain`.
+///
+/// Like `read`, a drain covers the contiguous block of records from the head to the end of the
+/// buffer, and the head is advanced once, past the messages taken, when the drain is dropped. Each
+/// `Message` borrows the drain so it cannot outlive the space it is read from.
+pub struct Drain<'a> {
+    buffer: &'a mut UnsafeBuffer,
+    head_position_index: i32,
+    head: i64,
+    head_index: i32,
+    contiguous_block_length: i32,
+    bytes_read: i32,
+    zero_on_release: bool,
+    stats: Option<&'a RingBufferStats>,
+}
+
+impl<'a> Drain<'a> {
+    pub(crate) fn new(buffer: &'a mut UnsafeBuffer, capacity: i32, head_position_index: i32, zero_on_release: bool, stats: Option<&'a RingBufferStats>) -> Self {
+        let head = buffer.get_long(head_position_index);
+        let head_index = head as i32 & (capacity - 1);
+
+        Drain {
+            buffer,
+            head_position_index,
+            head,
+            head_index,
+            contiguous_block_length: capacity - head_index,
+            bytes_read: 0,
+            zero_on_release,
+            stats,
+        }
+    }
+
+    /// The next message, or `None` once the block is exhausted or the next record is not yet
+    /// committed.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Message<'_>> {
+        while self.bytes_read < self.contiguous_block_length {
+            let record_index = self.head_index + self.bytes_read;
+            let record_length = self.buffer.get_int_volatile(length_offset(record_index));
+            if record_length <= 0 {
+                return None;
+            }
+
+            self.bytes_read += align(record_length, ALIGNMENT);
+
+            let message_type_id = self.buffer.get_int(type_offset(record_index));
+            if message_type_id == PADDING_MSG_TYPE_ID {
+                if let Some(stats) = self.stats {
+                    stats.on_padding();
+                }
+                continue;
+            }
+
+            return Some(Message::new(message_type_id, self.buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH));
+        }
+        None
+    }
+}
+
+impl Drop for Drain<'_> {
+    fn drop(&mut self) {
+        if self.bytes_read > 0 {
+            if self.zero_on_release {
+                self.buffer.set_memory(self.head_index, self.bytes_read, 0);
+            }
+            self.buffer.put_long_ordered(self.head_position_index, self.head + self.bytes_read as i64);
+        }
+    }
+}
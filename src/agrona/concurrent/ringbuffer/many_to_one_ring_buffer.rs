@@ -0,0 +1,492 @@
+use std::cell::UnsafeCell;
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::controlled_message_handler::Action;
+use crate::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use crate::agrona::concurrent::ringbuffer::drain::Drain;
+use crate::agrona::concurrent::ringbuffer::record_descriptor::{check_type_id, encoded_msg_offset, length_offset, make_header, scan_back_to_confirm_still_zeroed, type_offset, ALIGNMENT, HEADER_LENGTH};
+use crate::agrona::concurrent::ringbuffer::ring_buffer::{check_batch_length, check_msg_length, write_batch, ClaimIndex, RingBuffer, INSUFFICIENT_CAPACITY, PADDING_MSG_TYPE_ID};
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
+use crate::agrona::concurrent::ringbuffer::ring_buffer_descriptor::{check_capacity, CONSUMER_HEARTBEAT_OFFSET, CORRELATION_COUNTER_OFFSET, HEAD_CACHE_POSITION_OFFSET, HEAD_POSITION_OFFSET, TAIL_POSITION_OFFSET};
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::align;
+use std::cmp::max;
+
+const MIN_CAPACITY: i32 = HEADER_LENGTH;
+
+/// A ring-buffer that supports the exchange of messages from many producers to a single consumer.
+///
+/// Producers claim space by CAS on the tail position, so unlike `OneToOneRingBuffer` the claim is
+/// safe with concurrent writers. The consumer zeroes the space it has read before releasing it.
+///
+/// There is no `with_stats` as on `OneToOneRingBuffer`: `RingBufferStats` counters have a single
+/// writer, which many producers cannot share.
+pub struct ManyToOneRingBuffer {
+    capacity: i32,
+    max_msg_length: i32,
+    tail_position_index: i32,
+    head_cache_position_index: i32,
+    head_position_index: i32,
+    correlation_id_counter_index: i32,
+    consumer_heartbeat_index: i32,
+    buffer: UnsafeCell<UnsafeBuffer>,
+    heartbeat_clock: Option<Box<dyn EpochClock + Send + Sync>>,
+}
+
+unsafe impl Send for ManyToOneRingBuffer {}
+
+unsafe impl Sync for ManyToOneRingBuffer {}
+
+impl ManyToOneRingBuffer {
+    pub fn new(buffer: UnsafeBuffer) -> Self {
+        match Self::try_new(buffer) {
+            Ok(ring_buffer) => ring_buffer,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_new(buffer: UnsafeBuffer) -> Result<Self, RingBufferError> {
+        let capacity = check_capacity(buffer.capacity(), MIN_CAPACITY)?;
+        buffer.verify_alignment();
+        let max_msg_length = if MIN_CAPACITY == capacity {
+            0
+        } else {
+            max(HEADER_LENGTH, capacity >> 3)
+        };
+        let tail_position_index = capacity + TAIL_POSITION_OFFSET;
+        let head_cache_position_index = capacity + HEAD_CACHE_POSITION_OFFSET;
+        let head_position_index = capacity + HEAD_POSITION_OFFSET;
+        let correlation_id_counter_index = capacity + CORRELATION_COUNTER_OFFSET;
+        let consumer_heartbeat_index = capacity + CONSUMER_HEARTBEAT_OFFSET;
+        let buffer = UnsafeCell::new(buffer);
+
+        Ok(ManyToOneRingBuffer {
+            capacity,
+            max_msg_length,
+            tail_position_index,
+            head_cache_position_index,
+            head_position_index,
+            correlation_id_counter_index,
+            consumer_heartbeat_index,
+            buffer,
+            heartbeat_clock: None,
+        })
+    }
+
+    /// Stamp the consumer heartbeat with the time from `epoch_clock` on every read and drain, so
+    /// producers can check `is_consumer_alive`. The clock is also used by `is_consumer_alive`.
+    pub fn with_consumer_heartbeat<C>(mut self, epoch_clock: C) -> Self where C: EpochClock + Send + Sync + 'static {
+        self.heartbeat_clock = Some(Box::new(epoch_clock));
+        self
+    }
+
+    fn stamp_consumer_heartbeat(&self) {
+        if let Some(epoch_clock) = &self.heartbeat_clock {
+            Self::put_consumer_heartbeat_time(self, epoch_clock.time());
+        }
+    }
+
+    fn claim_capacity(&self, record_length: i32) -> i32 {
+        let aligned_record_length = align(record_length, ALIGNMENT);
+        let mask = self.capacity - 1;
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let mut head = buffer.get_long_volatile(self.head_cache_position_index);
+
+        let mut tail;
+        let mut tail_index;
+        let mut padding;
+        let mut write_index;
+        loop {
+            tail = buffer.get_long_volatile(self.tail_position_index);
+            let available_capacity = self.capacity - (tail - head) as i32;
+
+            if aligned_record_length > available_capacity {
+                head = buffer.get_long_volatile(self.head_position_index);
+                if aligned_record_length > self.capacity - (tail - head) as i32 {
+                    return INSUFFICIENT_CAPACITY;
+                }
+                buffer.put_long_ordered(self.head_cache_position_index, head);
+            }
+
+            padding = 0;
+            tail_index = tail as i32 & mask;
+            let to_buffer_end_length = self.capacity - tail_index;
+            write_index = tail_index;
+
+            if aligned_record_length > to_buffer_end_length {
+                write_index = 0;
+                let mut head_index = head as i32 & mask;
+
+                if aligned_record_length > head_index {
+                    head = buffer.get_long_volatile(self.head_position_index);
+                    head_index = head as i32 & mask;
+                    if aligned_record_length > head_index {
+                        return INSUFFICIENT_CAPACITY;
+                    }
+
+                    buffer.put_long_ordered(self.head_cache_position_index, head);
+                }
+
+                padding = to_buffer_end_length;
+            }
+
+            let next_tail = tail + aligned_record_length as i64 + padding as i64;
+            if buffer.compare_and_set_long(self.tail_position_index, tail, next_tail) {
+                break;
+            }
+        }
+
+        if padding != 0 {
+            buffer.put_long_ordered(tail_index, make_header(padding, PADDING_MSG_TYPE_ID));
+        }
+
+        write_index
+    }
+
+    fn compute_record_index(&self, index: i32) -> Result<i32, RingBufferError> {
+        let record_index = index - HEADER_LENGTH;
+        if record_index < 0 || record_index > (self.capacity - HEADER_LENGTH) {
+            return Err(RingBufferError::InvalidIndex(index));
+        }
+        Ok(record_index)
+    }
+
+    fn verify_claimed_space_not_released(&self, buffer: &UnsafeBuffer, record_index: i32) -> Result<i32, RingBufferError> {
+        let record_length = buffer.get_int(length_offset(record_index));
+        if record_length < 0 {
+            return Ok(record_length);
+        }
+        if PADDING_MSG_TYPE_ID == buffer.get_int(type_offset(record_index)) {
+            Err(RingBufferError::ClaimAborted)
+        } else {
+            Err(RingBufferError::ClaimCommitted)
+        }
+    }
+
+    fn release(&self, buffer: &mut UnsafeBuffer, head: i64, head_index: i32, bytes_read: i32) {
+        buffer.set_memory(head_index, bytes_read, 0);
+        buffer.put_long_ordered(self.head_position_index, head + bytes_read as i64);
+    }
+}
+
+impl RingBuffer for ManyToOneRingBuffer {
+    fn capacity(&self) -> i32 {
+        self.capacity
+    }
+
+    fn try_write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> Result<(), RingBufferError> {
+        check_type_id(msg_type_id)?;
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        check_msg_length(length, self.max_msg_length)?;
+
+        let record_length = length + HEADER_LENGTH;
+        let record_index = Self::claim_capacity(self, record_length);
+
+        if record_index == INSUFFICIENT_CAPACITY {
+            return Err(RingBufferError::InsufficientCapacity)
+        }
+
+        buffer.put_long_ordered(record_index, make_header(-record_length, msg_type_id));
+        buffer.put_bytes2(encoded_msg_offset(record_index), src_buffer, offset, length);
+        buffer.put_int_ordered(length_offset(record_index), record_length);
+
+        Ok(())
+    }
+
+    fn try_write_batch(&self, messages: &[(i32, &[u8])]) -> Result<(), RingBufferError> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let batch_length = check_batch_length(self.capacity, self.max_msg_length, messages)?;
+        let first_index = Self::claim_capacity(self, batch_length);
+
+        if first_index == INSUFFICIENT_CAPACITY {
+            return Err(RingBufferError::InsufficientCapacity)
+        }
+
+        write_batch(buffer, first_index, messages);
+
+        Ok(())
+    }
+
+    fn try_claim(&self, msg_type_id: i32, length: i32) -> Result<ClaimIndex, RingBufferError> {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        check_type_id(msg_type_id)?;
+        check_msg_length(length, self.max_msg_length)?;
+
+        let record_length = length + HEADER_LENGTH;
+        let record_index = Self::claim_capacity(self, record_length);
+
+        if record_index == INSUFFICIENT_CAPACITY {
+            return Err(RingBufferError::InsufficientCapacity)
+        }
+
+        buffer.put_long_ordered(record_index, make_header(-record_length, msg_type_id));
+
+        Ok(ClaimIndex(encoded_msg_offset(record_index)))
+    }
+
+    fn try_commit(&self, index: i32) -> Result<(), RingBufferError> {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let record_index = Self::compute_record_index(self, index)?;
+        let record_length = Self::verify_claimed_space_not_released(self, buffer, record_index)?;
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
+        Ok(())
+    }
+
+    fn try_abort(&self, index: i32) -> Result<(), RingBufferError> {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let record_index = Self::compute_record_index(self, index)?;
+        let record_length = Self::verify_claimed_space_not_released(self, buffer, record_index)?;
+
+        buffer.put_int(type_offset(record_index), PADDING_MSG_TYPE_ID);
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
+        Ok(())
+    }
+
+    fn read<F>(&self, func: F) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        Self::read0(self, func, i32::MAX)
+    }
+
+    fn read0<F>(&self, mut func: F, message_count_limit: i32) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        Self::stamp_consumer_heartbeat(self);
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let mut messages_read = 0;
+
+        let head = buffer.get_long(self.head_position_index);
+
+        let mut bytes_read = 0;
+
+        let capacity = self.capacity;
+        let head_index = head as i32 & (capacity - 1);
+        let contiguous_block_length = capacity - head_index;
+
+        while (bytes_read < contiguous_block_length) && (messages_read < message_count_limit) {
+            let record_index = head_index + bytes_read;
+            let record_length = buffer.get_int_volatile(length_offset(record_index));
+            if record_length <= 0 {
+                break;
+            }
+
+            bytes_read += align(record_length, ALIGNMENT);
+
+            let message_type_id = buffer.get_int(type_offset(record_index));
+            if message_type_id == PADDING_MSG_TYPE_ID {
+                continue;
+            }
+
+            func(message_type_id, buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
+            messages_read += 1;
+        }
+        if bytes_read > 0 {
+            Self::release(self, buffer, head, head_index, bytes_read);
+        }
+        messages_read
+    }
+
+    fn drain(&self) -> Drain<'_> {
+        Self::stamp_consumer_heartbeat(self);
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        Drain::new(buffer, self.capacity, self.head_position_index, true, None)
+    }
+
+    fn controlled_read<F>(&self, func: F) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
+        Self::controlled_read0(self, func, i32::MAX)
+    }
+
+    fn controlled_read0<F>(&self, func: F, message_count_limit: i32) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
+        Self::stamp_consumer_heartbeat(self);
+        let mut messages_read = 0;
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+
+        let mut head = buffer.get_long(self.head_position_index);
+
+        let mut bytes_read = 0;
+
+        let capacity = self.capacity;
+        let mut head_index = head as i32 & (capacity - 1);
+        let contiguous_block_length = capacity - head_index;
+
+        while (bytes_read < contiguous_block_length) && (messages_read < message_count_limit) {
+            let record_index = head_index + bytes_read;
+            let record_length = buffer.get_int_volatile(length_offset(record_index));
+            if record_length <= 0 {
+                break;
+            }
+
+            let aligned_length = align(record_length, ALIGNMENT);
+            bytes_read += aligned_length;
+
+            let message_type_id = buffer.get_int(type_offset(record_index));
+            if message_type_id == PADDING_MSG_TYPE_ID {
+                continue;
+            }
+
+            let action = func(message_type_id, buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
+
+            if let Action::ABORT = action {
+                bytes_read -= aligned_length;
+                break;
+            }
+
+            messages_read += 1;
+
+            match action {
+                Action::BREAK => break,
+                Action::COMMIT => {
+                    Self::release(self, buffer, head, head_index, bytes_read);
+                    head_index += bytes_read;
+                    head += bytes_read as i64;
+                    bytes_read = 0;
+                }
+                _ => {}
+            }
+        }
+        if bytes_read > 0 {
+            Self::release(self, buffer, head, head_index, bytes_read);
+        }
+
+        messages_read
+    }
+
+    fn max_msg_length(&self) -> i32 {
+        self.max_msg_length
+    }
+
+    fn next_correlation_id(&self) -> i64 {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        buffer.get_and_add_long(self.correlation_id_counter_index, 1)
+    }
+
+    fn buffer(&self) -> &mut UnsafeBuffer {
+        unsafe {
+            &mut *self.buffer.get()
+        }
+    }
+
+    fn put_consumer_heartbeat_time(&self, time: i64) {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        buffer.put_long_ordered(self.consumer_heartbeat_index, time);
+    }
+
+    fn consumer_heartbeat_time(&self) -> i64 {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        buffer.get_long_volatile(self.consumer_heartbeat_index)
+    }
+
+    fn heartbeat_clock_time(&self) -> i64 {
+        match &self.heartbeat_clock {
+            Some(epoch_clock) => epoch_clock.time(),
+            None => SystemEpochClock.time(),
+        }
+    }
+
+    fn producer_position(&self) -> i64 {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        buffer.get_long_volatile(self.tail_position_index)
+    }
+
+    fn consumer_position(&self) -> i64 {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        buffer.get_long_volatile(self.head_position_index)
+    }
+
+    fn size(&self) -> i32 {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let mut head_before;
+        let mut tail;
+        let mut head_after = buffer.get_long_volatile(self.head_position_index);
+
+        loop {
+            head_before = head_after;
+            tail = buffer.get_long_volatile(self.tail_position_index);
+            head_after = buffer.get_long_volatile(self.head_position_index);
+
+            if head_after == head_before {
+                break;
+            }
+        }
+
+        let size = tail - head_after;
+        if size < 0 {
+            return 0;
+        } else if size > self.capacity as i64 {
+            return self.capacity;
+        }
+
+        size as i32
+    }
+
+    fn unblock(&self) -> bool {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let head_position = buffer.get_long_volatile(self.head_position_index);
+        let tail_position = buffer.get_long_volatile(self.tail_position_index);
+
+        if head_position == tail_position {
+            return false;
+        }
+
+        let mask = self.capacity - 1;
+        let consumer_index = head_position as i32 & mask;
+        let producer_index = tail_position as i32 & mask;
+
+        let mut unblocked = false;
+        let length = buffer.get_int_volatile(length_offset(consumer_index));
+        if length < 0 {
+            buffer.put_int(type_offset(consumer_index), PADDING_MSG_TYPE_ID);
+            buffer.put_int_ordered(length_offset(consumer_index), -length);
+            unblocked = true;
+        } else if length == 0 {
+            // scan from the consumer up to the producer, or the end of the buffer, for the next record
+            let limit = if producer_index > consumer_index { producer_index } else { self.capacity };
+            let mut i = consumer_index + ALIGNMENT;
+
+            while i < limit {
+                if buffer.get_int_volatile(i) != 0 {
+                    if scan_back_to_confirm_still_zeroed(buffer, i, consumer_index) {
+                        buffer.put_int(type_offset(consumer_index), PADDING_MSG_TYPE_ID);
+                        buffer.put_int_ordered(length_offset(consumer_index), i - consumer_index);
+                        unblocked = true;
+                    }
+                    break;
+                }
+                i += ALIGNMENT;
+            }
+        }
+
+        unblocked
+    }
+}
@@ -0,0 +1,75 @@
+use std::slice;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+
+/// A message read from a ring buffer, borrowed in place from the underlying buffer.
+///
+/// Offsets passed to the getters are relative to the start of the encoded message, and reads past
+/// `length()` panic like slice indexing does.
+#[derive(Clone, Copy)]
+pub struct Message<'a> {
+    msg_type_id: i32,
+    buffer: &'a UnsafeBuffer,
+    index: i32,
+    length: i32,
+}
+
+impl<'a> Message<'a> {
+    pub(crate) fn new(msg_type_id: i32, buffer: &'a UnsafeBuffer, index: i32, length: i32) -> Self {
+        Message {
+            msg_type_id,
+            buffer,
+            index,
+            length,
+        }
+    }
+
+    pub fn type_id(&self) -> i32 {
+        self.msg_type_id
+    }
+
+    /// Index in the ring buffer's underlying buffer at which the message begins.
+    pub fn index(&self) -> i32 {
+        self.index
+    }
+
+    pub fn length(&self) -> i32 {
+        self.length
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        unsafe {
+            let ptr = self.buffer.byte_array().add((self.buffer.address_offset() + self.index) as usize);
+            slice::from_raw_parts(ptr, self.length as usize)
+        }
+    }
+
+    pub fn get_byte(&self, offset: i32) -> u8 {
+        self.as_bytes()[offset as usize]
+    }
+
+    pub fn get_short(&self, offset: i32) -> i16 {
+        i16::from_ne_bytes(self.get_bytes(offset))
+    }
+
+    pub fn get_int(&self, offset: i32) -> i32 {
+        i32::from_ne_bytes(self.get_bytes(offset))
+    }
+
+    pub fn get_long(&self, offset: i32) -> i64 {
+        i64::from_ne_bytes(self.get_bytes(offset))
+    }
+
+    pub fn get_float(&self, offset: i32) -> f32 {
+        f32::from_ne_bytes(self.get_bytes(offset))
+    }
+
+    pub fn get_double(&self, offset: i32) -> f64 {
+        f64::from_ne_bytes(self.get_bytes(offset))
+    }
+
+    fn get_bytes<const N: usize>(&self, offset: i32) -> [u8; N] {
+        let offset = offset as usize;
+        self.as_bytes()[offset..offset + N].try_into().unwrap()
+    }
+}
@@ -2,3 +2,12 @@ pub mod record_descriptor;
 pub mod ring_buffer_descriptor;
 pub mod ring_buffer;
 pub mod one_to_one_ring_buffer;
+pub mod many_to_one_ring_buffer;
+pub mod ring_buffer_unblocker;
+pub mod split;
+pub mod ring_buffer_error;
+pub mod message;
+pub mod drain;
+#[cfg(feature = "async")]
+pub mod async_ring_buffer;
+pub mod ring_buffer_stats;
@@ -1,14 +1,17 @@
 use std::cell::UnsafeCell;
 use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
 use crate::agrona::concurrent::controlled_message_handler::Action;
-use crate::agrona::concurrent::ringbuffer::record_descriptor::{check_type_id, encoded_msg_offset, length_offset, type_offset, ALIGNMENT, HEADER_LENGTH};
-use crate::agrona::concurrent::ringbuffer::ring_buffer::{RingBuffer, INSUFFICIENT_CAPACITY, PADDING_MSG_TYPE_ID};
+use crate::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use crate::agrona::concurrent::ringbuffer::drain::Drain;
+use crate::agrona::concurrent::ringbuffer::record_descriptor::{check_type_id, encoded_msg_offset, length_offset, make_header, scan_back_to_confirm_still_zeroed, type_offset, ALIGNMENT, HEADER_LENGTH};
+use crate::agrona::concurrent::ringbuffer::ring_buffer::{check_batch_length, check_msg_length, write_batch, ClaimIndex, RingBuffer, INSUFFICIENT_CAPACITY, PADDING_MSG_TYPE_ID};
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
+use crate::agrona::concurrent::ringbuffer::ring_buffer_stats::RingBufferStats;
 use crate::agrona::concurrent::ringbuffer::ring_buffer_descriptor::{check_capacity, CONSUMER_HEARTBEAT_OFFSET, CORRELATION_COUNTER_OFFSET, HEAD_CACHE_POSITION_OFFSET, HEAD_POSITION_OFFSET, TAIL_POSITION_OFFSET};
 use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
 use crate::agrona::direct_buffer::DirectBuffer;
 use crate::bit_util::align;
 use std::cmp::max;
-use std::sync::atomic::{fence, Ordering};
 
 const MIN_CAPACITY: i32 = HEADER_LENGTH << 1;
 
@@ -20,7 +23,9 @@ pub struct OneToOneRingBuffer {
     head_position_index: i32,
     correlation_id_counter_index: i32,
     consumer_heartbeat_index: i32,
-    buffer: UnsafeCell<UnsafeBuffer>
+    buffer: UnsafeCell<UnsafeBuffer>,
+    stats: Option<RingBufferStats>,
+    heartbeat_clock: Option<Box<dyn EpochClock + Send + Sync>>,
 }
 
 unsafe impl Send for OneToOneRingBuffer {}
@@ -29,7 +34,14 @@ unsafe impl Sync for OneToOneRingBuffer {}
 
 impl OneToOneRingBuffer {
     pub fn new(buffer: UnsafeBuffer) -> Self {
-        let capacity = check_capacity(buffer.capacity(), MIN_CAPACITY);
+        match Self::try_new(buffer) {
+            Ok(ring_buffer) => ring_buffer,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_new(buffer: UnsafeBuffer) -> Result<Self, RingBufferError> {
+        let capacity = check_capacity(buffer.capacity(), MIN_CAPACITY)?;
         buffer.verify_alignment();
         let max_msg_length = if MIN_CAPACITY == capacity {
             0
@@ -43,7 +55,7 @@ impl OneToOneRingBuffer {
         let consumer_heartbeat_index = capacity + CONSUMER_HEARTBEAT_OFFSET;
         let buffer = UnsafeCell::new(buffer);
 
-        OneToOneRingBuffer {
+        Ok(OneToOneRingBuffer {
             capacity,
             max_msg_length,
             tail_position_index,
@@ -51,15 +63,56 @@ impl OneToOneRingBuffer {
             head_position_index,
             correlation_id_counter_index,
             consumer_heartbeat_index,
-            buffer
+            buffer,
+            stats: None,
+            heartbeat_clock: None,
+        })
+    }
+
+    /// Maintain `stats` as messages are written and read.
+    pub fn with_stats(mut self, stats: RingBufferStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    pub fn stats(&self) -> Option<&RingBufferStats> {
+        self.stats.as_ref()
+    }
+
+    /// Stamp the consumer heartbeat with the time from `epoch_clock` on every read and drain, so
+    /// producers can check `is_consumer_alive`. The clock is also used by `is_consumer_alive`.
+    pub fn with_consumer_heartbeat<C>(mut self, epoch_clock: C) -> Self where C: EpochClock + Send + Sync + 'static {
+        self.heartbeat_clock = Some(Box::new(epoch_clock));
+        self
+    }
+
+    fn stamp_consumer_heartbeat(&self) {
+        if let Some(epoch_clock) = &self.heartbeat_clock {
+            Self::put_consumer_heartbeat_time(self, epoch_clock.time());
         }
     }
 
-    fn check_msg_length(&self, length: i32) {
-        if length < 0 {
-            panic!("Invalid message length={}", length);
-        } else if length > self.max_msg_length {
-            panic!("Encoded message exceeds max_msg_length={}", length);
+    fn on_written(&self, messages: i64, bytes: i64) {
+        if let Some(stats) = &self.stats {
+            let buffer = unsafe {
+                &mut *self.buffer.get()
+            };
+            // the producer owns the tail so only the head needs a volatile load, unlike size()
+            let fill_level = buffer.get_long(self.tail_position_index) - buffer.get_long_volatile(self.head_position_index);
+            stats.on_written(messages, bytes, fill_level.clamp(0, self.capacity as i64) as i32);
+        }
+    }
+
+    fn insufficient_capacity(&self) -> RingBufferError {
+        if let Some(stats) = &self.stats {
+            stats.on_claim_failure();
+        }
+        RingBufferError::InsufficientCapacity
+    }
+
+    fn on_padding(&self) {
+        if let Some(stats) = &self.stats {
+            stats.on_padding();
         }
     }
 
@@ -87,10 +140,8 @@ impl OneToOneRingBuffer {
         let to_buffer_end_length = self.capacity - record_index;
         let mut write_index = record_index;
         let mut next_tail = tail + aligned_record_length as i64;
-        // println!("tail, arl, next_tail: {} {} {}", tail, aligned_record_length, next_tail);
 
         if aligned_record_length == to_buffer_end_length { // message fits within the end of the buffer
-            // println!("here6: {}", next_tail);
             buffer.put_long_ordered(self.tail_position_index, next_tail);
             buffer.put_long(0, 0); // pre-zero next message header
             return record_index;
@@ -114,12 +165,10 @@ impl OneToOneRingBuffer {
         }
 
         buffer.put_long_ordered(self.tail_position_index, next_tail);
-        // println!("here2: {}, {}", next_tail, buffer.get_long(self.tail_position_index));
 
         if padding != 0 {
             buffer.put_long(0, 0);
             buffer.put_int_ordered(length_offset(record_index), -padding);
-            // fence(Ordering::Release);
 
             buffer.put_int(type_offset(record_index), PADDING_MSG_TYPE_ID);
             buffer.put_int_ordered(length_offset(record_index), padding);
@@ -127,29 +176,28 @@ impl OneToOneRingBuffer {
 
         if write_index != INSUFFICIENT_CAPACITY {
             buffer.put_long(write_index + aligned_record_length, 0); // pre-zero next message header
-            // println!("here3: {}, {}", 0, buffer.get_long(write_index + aligned_record_length));
         }
 
         write_index
     }
 
-    fn compute_record_index(&self, index: i32) -> i32 {
+    fn compute_record_index(&self, index: i32) -> Result<i32, RingBufferError> {
         let record_index = index - HEADER_LENGTH;
         if record_index < 0 || record_index > (self.capacity - HEADER_LENGTH) {
-            panic!("Invalid message index={}", index);
+            return Err(RingBufferError::InvalidIndex(index));
         }
-        record_index
+        Ok(record_index)
     }
 
-    fn verify_claimed_space_not_released(&self, buffer: &UnsafeBuffer, record_index: i32) -> i32 {
+    fn verify_claimed_space_not_released(&self, buffer: &UnsafeBuffer, record_index: i32) -> Result<i32, RingBufferError> {
         let record_length = buffer.get_int(length_offset(record_index));
         if record_length < 0 {
-            return record_length;
+            return Ok(record_length);
         }
         if PADDING_MSG_TYPE_ID == buffer.get_int(type_offset(record_index)) {
-            panic!("claimed space previously aborted");
+            Err(RingBufferError::ClaimAborted)
         } else {
-            panic!("claimed space previously committed");
+            Err(RingBufferError::ClaimCommitted)
         }
     }
 }
@@ -159,73 +207,96 @@ impl RingBuffer for OneToOneRingBuffer {
         self.capacity
     }
 
-    fn write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> bool {
-        check_type_id(msg_type_id);
+    fn try_write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> Result<(), RingBufferError> {
+        check_type_id(msg_type_id)?;
         let buffer = unsafe {
             &mut *self.buffer.get()
         };
-        Self::check_msg_length(self, length);
+        check_msg_length(length, self.max_msg_length)?;
 
         let record_length = length + HEADER_LENGTH;
         let record_index = Self::claim_capacity(self, record_length);
 
         if record_index == INSUFFICIENT_CAPACITY {
-            return false
+            return Err(Self::insufficient_capacity(self))
         }
 
-        buffer.put_int_ordered(length_offset(record_index), -1 * record_length);
-        // fence(Ordering::Release);
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
 
         buffer.put_bytes2(encoded_msg_offset(record_index), src_buffer, offset, length);
         buffer.put_int(type_offset(record_index), msg_type_id);
         buffer.put_int_ordered(length_offset(record_index), record_length);
+        Self::on_written(self, 1, length as i64);
+
+        Ok(())
+    }
+
+    fn try_write_batch(&self, messages: &[(i32, &[u8])]) -> Result<(), RingBufferError> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let batch_length = check_batch_length(self.capacity, self.max_msg_length, messages)?;
+        let first_index = Self::claim_capacity(self, batch_length);
+
+        if first_index == INSUFFICIENT_CAPACITY {
+            return Err(Self::insufficient_capacity(self))
+        }
+
+        let batch_length_without_headers = messages.iter().map(|(_, bytes)| bytes.len() as i64).sum();
+        write_batch(buffer, first_index, messages);
+        Self::on_written(self, messages.len() as i64, batch_length_without_headers);
 
-        true
+        Ok(())
     }
 
-    fn try_claim(&self, msg_type_id: i32, length: i32) -> i32 {
+    fn try_claim(&self, msg_type_id: i32, length: i32) -> Result<ClaimIndex, RingBufferError> {
         let buffer = unsafe {
             &mut *self.buffer.get()
         };
-        check_type_id(msg_type_id);
-        Self::check_msg_length(self, length);
+        check_type_id(msg_type_id)?;
+        check_msg_length(length, self.max_msg_length)?;
 
         let record_length = length + HEADER_LENGTH;
         let record_index = Self::claim_capacity(self, record_length);
 
         if record_index == INSUFFICIENT_CAPACITY {
-            return record_index
+            return Err(Self::insufficient_capacity(self))
         }
 
 
-        buffer.put_int_ordered(length_offset(record_index), -1 * record_length);
-        // println!("here: {}, {}", -1 * record_length, buffer.get_int(length_offset(record_index)));
-        // fence(Ordering::Release);
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
         buffer.put_int(type_offset(record_index), msg_type_id);
-        // println!("here1: {}, {}", msg_type_id, buffer.get_int(type_offset(record_index)));
 
-        encoded_msg_offset(record_index)
+        Ok(ClaimIndex(encoded_msg_offset(record_index)))
     }
 
-    fn commit(&self, index: i32) {
+    fn try_commit(&self, index: i32) -> Result<(), RingBufferError> {
         let buffer = unsafe {
             &mut *self.buffer.get()
         };
-        let record_index = Self::compute_record_index(&self, index);
-        let record_length = Self::verify_claimed_space_not_released(&self, &buffer, record_index);
-        buffer.put_int_ordered(length_offset(record_index), -1 * record_length);
-        // println!("here4: {}, {}", -1 * record_length, buffer.get_int(length_offset(record_index)));
+        let record_index = Self::compute_record_index(self, index)?;
+        let record_length = Self::verify_claimed_space_not_released(self, buffer, record_index)?;
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
+        Self::on_written(self, 1, (-record_length - HEADER_LENGTH) as i64);
+        Ok(())
     }
 
-    fn abort(&self, index: i32) {
+    fn try_abort(&self, index: i32) -> Result<(), RingBufferError> {
         let buffer = unsafe {
             &mut *self.buffer.get()
         };
-        let record_index = Self::compute_record_index(&self, index);
-        let record_length = Self::verify_claimed_space_not_released(&self, &buffer, record_index);
+        let record_index = Self::compute_record_index(self, index)?;
+        let record_length = Self::verify_claimed_space_not_released(self, buffer, record_index)?;
 
         buffer.put_int(type_offset(record_index), PADDING_MSG_TYPE_ID);
-        buffer.put_int_ordered(length_offset(record_index), -1 * record_length);
+        buffer.put_int_ordered(length_offset(record_index), -record_length);
+        if let Some(stats) = &self.stats {
+            stats.on_abort();
+        }
+        Ok(())
     }
 
     // this means dynamically dispatched trait object
@@ -234,6 +305,7 @@ impl RingBuffer for OneToOneRingBuffer {
     }
 
     fn read0<F>(&self, mut func: F, message_count_limit: i32) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        Self::stamp_consumer_heartbeat(self);
         let buffer = unsafe {
             &mut *self.buffer.get()
         };
@@ -259,10 +331,11 @@ impl RingBuffer for OneToOneRingBuffer {
 
             let message_type_id = buffer.get_int(type_offset(record_index));
             if message_type_id == PADDING_MSG_TYPE_ID {
+                Self::on_padding(self);
                 continue;
             }
 
-            func(message_type_id, &buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
+            func(message_type_id, buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
             messages_read += 1;
         }
         if bytes_read > 0 {
@@ -271,11 +344,20 @@ impl RingBuffer for OneToOneRingBuffer {
         messages_read
     }
 
+    fn drain(&self) -> Drain<'_> {
+        Self::stamp_consumer_heartbeat(self);
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        Drain::new(buffer, self.capacity, self.head_position_index, false, self.stats.as_ref())
+    }
+
     fn controlled_read<F>(&self, func: F) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
         Self::controlled_read0(self, func, i32::MAX)
     }
 
     fn controlled_read0<F>(&self, func: F, message_count_limit: i32) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
+        Self::stamp_consumer_heartbeat(self);
         let mut messages_read = 0;
         let buffer = unsafe {
             &mut *self.buffer.get()
@@ -303,10 +385,11 @@ impl RingBuffer for OneToOneRingBuffer {
 
             let message_type_id = buffer.get_int(type_offset(record_index));
             if message_type_id == PADDING_MSG_TYPE_ID {
+                Self::on_padding(self);
                 continue;
             }
 
-            let action = func(message_type_id, &buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
+            let action = func(message_type_id, buffer, record_index + HEADER_LENGTH, record_length - HEADER_LENGTH);
 
             messages_read += 1;
 
@@ -347,10 +430,9 @@ impl RingBuffer for OneToOneRingBuffer {
     }
 
     fn buffer(&self) -> &mut UnsafeBuffer {
-        let buffer = unsafe {
+        unsafe {
             &mut *self.buffer.get()
-        };
-        buffer
+        }
     }
 
     fn put_consumer_heartbeat_time(&self, time: i64) {
@@ -367,6 +449,13 @@ impl RingBuffer for OneToOneRingBuffer {
         buffer.get_long_volatile(self.consumer_heartbeat_index)
     }
 
+    fn heartbeat_clock_time(&self) -> i64 {
+        match &self.heartbeat_clock {
+            Some(epoch_clock) => epoch_clock.time(),
+            None => SystemEpochClock.time(),
+        }
+    }
+
     fn producer_position(&self) -> i64 {
         let buffer = unsafe {
             &mut *self.buffer.get()
@@ -394,7 +483,7 @@ impl RingBuffer for OneToOneRingBuffer {
             tail = buffer.get_long_volatile(self.tail_position_index);
             head_after = buffer.get_long_volatile(self.head_position_index);
 
-            if head_before == head_before {
+            if head_before == head_after {
                 break;
             }
         }
@@ -410,6 +499,37 @@ impl RingBuffer for OneToOneRingBuffer {
     }
 
     fn unblock(&self) -> bool {
+        let buffer = unsafe {
+            &mut *self.buffer.get()
+        };
+        let head_position = buffer.get_long_volatile(self.head_position_index);
+        let tail_position = buffer.get_long_volatile(self.tail_position_index);
+
+        if head_position == tail_position {
+            return false;
+        }
+
+        let mask = self.capacity - 1;
+        let consumer_index = head_position as i32 & mask;
+        let producer_index = tail_position as i32 & mask;
+
+        let length = buffer.get_int_volatile(length_offset(consumer_index));
+        if length < 0 {
+            buffer.put_int(type_offset(consumer_index), PADDING_MSG_TYPE_ID);
+            buffer.put_int_ordered(length_offset(consumer_index), -length);
+            return true;
+        } else if length == 0 && producer_index > consumer_index {
+            // the producer moved the tail but has not written the header, and may only be slow,
+            // so pad only while nothing it claimed has been written. Stale bytes from an earlier
+            // lap also count as written, which errs on leaving the record blocked. The header is
+            // set with a CAS so one the producer writes meanwhile is kept, and one it writes later
+            // replaces the padding before the consumer has moved past it.
+            if scan_back_to_confirm_still_zeroed(buffer, producer_index, consumer_index) {
+                let padding = make_header(producer_index - consumer_index, PADDING_MSG_TYPE_ID);
+                return buffer.compare_and_set_long(consumer_index, 0, padding);
+            }
+        }
+
         false
     }
 }
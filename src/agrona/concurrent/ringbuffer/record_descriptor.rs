@@ -1,3 +1,6 @@
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
 use crate::bit_util::SIZE_OF_INT;
 
 pub const HEADER_LENGTH: i32 = SIZE_OF_INT << 1;
@@ -16,8 +19,27 @@ pub fn encoded_msg_offset(record_type: i32) -> i32 {
     record_type + HEADER_LENGTH
 }
 
-pub fn check_type_id(msg_type_id: i32) {
+pub fn check_type_id(msg_type_id: i32) -> Result<(), RingBufferError> {
     if msg_type_id < 1 {
-        panic!("message type id must be greater than zero, msgTypeId={}", msg_type_id)
+        return Err(RingBufferError::InvalidTypeId(msg_type_id));
     }
+    Ok(())
+}
+
+/// Make the header for a record as a single long so it can be published with one ordered write.
+pub fn make_header(length: i32, msg_type_id: i32) -> i64 {
+    ((msg_type_id as i64 & 0xFFFF_FFFF) << 32) | (length as i64 & 0xFFFF_FFFF)
+}
+
+/// Whether every aligned slot from just before `from` back to `limit` is still zero, i.e. no
+/// producer has started writing a record there.
+pub(crate) fn scan_back_to_confirm_still_zeroed(buffer: &UnsafeBuffer, from: i32, limit: i32) -> bool {
+    let mut i = from - ALIGNMENT;
+    while i >= limit {
+        if buffer.get_int_volatile(i) != 0 {
+            return false;
+        }
+        i -= ALIGNMENT;
+    }
+    true
 }
@@ -1,9 +1,79 @@
+use std::ops::{Deref, DerefMut};
+use std::slice;
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
 use crate::agrona::concurrent::controlled_message_handler::Action;
+use crate::agrona::concurrent::ringbuffer::drain::Drain;
+use crate::agrona::concurrent::ringbuffer::message::Message;
+use crate::agrona::concurrent::ringbuffer::record_descriptor::{check_type_id, encoded_msg_offset, length_offset, make_header, ALIGNMENT, HEADER_LENGTH};
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
 use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::align;
 
 pub const PADDING_MSG_TYPE_ID: i32 = -1;
 pub const INSUFFICIENT_CAPACITY: i32 = -2;
 
+/// Index in `RingBuffer::buffer` at which a message claimed with `try_claim` should be encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimIndex(pub(crate) i32);
+
+impl ClaimIndex {
+    pub fn index(self) -> i32 {
+        self.0
+    }
+}
+
+impl From<ClaimIndex> for i32 {
+    fn from(index: ClaimIndex) -> i32 {
+        index.0
+    }
+}
+
+pub(crate) fn check_msg_length(length: i32, max_msg_length: i32) -> Result<(), RingBufferError> {
+    if length < 0 {
+        return Err(RingBufferError::InvalidMessageLength(length));
+    } else if length > max_msg_length {
+        return Err(RingBufferError::MessageTooLong { length, max_msg_length });
+    }
+    Ok(())
+}
+
+/// Length of the records for a batch written with `RingBuffer::try_write_batch`, checking each
+/// message and that the records fit in half the capacity.
+pub(crate) fn check_batch_length(capacity: i32, max_msg_length: i32, messages: &[(i32, &[u8])]) -> Result<i32, RingBufferError> {
+    let max_batch_length = (capacity >> 1) - HEADER_LENGTH;
+    let mut batch_length = 0;
+    for (msg_type_id, bytes) in messages {
+        check_type_id(*msg_type_id)?;
+        let length = i32::try_from(bytes.len()).unwrap_or(i32::MAX);
+        check_msg_length(length, max_msg_length)?;
+        batch_length += align(length + HEADER_LENGTH, ALIGNMENT);
+        if batch_length > max_batch_length {
+            return Err(RingBufferError::BatchTooLong { length: batch_length, max_batch_length });
+        }
+    }
+    Ok(batch_length)
+}
+
+/// Write a non-empty batch into the space claimed for it at `first_index`. The records behind the
+/// first are only visible once its length is published, so the consumer sees all or none.
+pub(crate) fn write_batch(buffer: &mut UnsafeBuffer, first_index: i32, messages: &[(i32, &[u8])]) {
+    let (first_type_id, first_bytes) = messages[0];
+    let first_record_length = first_bytes.len() as i32 + HEADER_LENGTH;
+    buffer.put_long_ordered(first_index, make_header(-first_record_length, first_type_id));
+
+    let mut record_index = first_index + align(first_record_length, ALIGNMENT);
+    for (msg_type_id, bytes) in &messages[1..] {
+        let record_length = bytes.len() as i32 + HEADER_LENGTH;
+        buffer.put_bytes(encoded_msg_offset(record_index), bytes);
+        buffer.put_long(record_index, make_header(record_length, *msg_type_id));
+        record_index += align(record_length, ALIGNMENT);
+    }
+
+    buffer.put_bytes(encoded_msg_offset(first_index), first_bytes);
+    buffer.put_int_ordered(length_offset(first_index), first_record_length);
+}
+
 pub trait RingBuffer {
 
     fn capacity(&self) -> i32;
@@ -16,18 +86,104 @@ pub trait RingBuffer {
     /// @param length    of the encoded message in bytes.
     /// @return true if written to the ring-buffer, or false if insufficient space exists.
     /// @throws IllegalArgumentException if the {@code length} is negative or is greater than {@link #maxMsgLength()}.
-    fn write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> bool;
+    fn write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> bool {
+        match self.try_write(msg_type_id, src_buffer, offset, length) {
+            Ok(()) => true,
+            Err(RingBufferError::InsufficientCapacity) => false,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Non-blocking write of a message which reports a full buffer, an invalid type id or an
+    /// invalid length as an error rather than `false` or a panic.
+    fn try_write(&self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> Result<(), RingBufferError>;
+
+    /// Write a message gathered from several source slices, e.g. a header and a payload held in
+    /// different buffers, into a single record.
+    ///
+    /// @return true if written to the ring-buffer, or false if insufficient space exists.
+    fn write_vectored(&self, msg_type_id: i32, srcs: &[&[u8]]) -> bool where Self: Sized {
+        match self.try_write_vectored(msg_type_id, srcs) {
+            Ok(()) => true,
+            Err(RingBufferError::InsufficientCapacity) => false,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    fn try_write_vectored(&self, msg_type_id: i32, srcs: &[&[u8]]) -> Result<(), RingBufferError> where Self: Sized {
+        let length = srcs.iter().map(|src| src.len()).sum::<usize>();
+        // the claim aborts on drop, so a panic while copying does not leave the record claimed
+        let mut claim = self.claim(msg_type_id, i32::try_from(length).unwrap_or(i32::MAX))?;
+
+        let mut offset = 0;
+        for src in srcs {
+            claim[offset..offset + src.len()].copy_from_slice(src);
+            offset += src.len();
+        }
+
+        claim.commit()
+    }
+
+    /// Write a batch of `(msg_type_id, bytes)` messages as consecutive records with a single tail
+    /// update, so the consumer sees either all of them or none. The records, including headers and
+    /// alignment, must fit in half the capacity.
+    ///
+    /// @return true if written to the ring-buffer, or false if insufficient space exists.
+    fn write_batch(&self, messages: &[(i32, &[u8])]) -> bool {
+        match self.try_write_batch(messages) {
+            Ok(()) => true,
+            Err(RingBufferError::InsufficientCapacity) => false,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
-    fn try_claim(&self, msg_type_id: i32, length: i32) -> i32;
+    fn try_write_batch(&self, messages: &[(i32, &[u8])]) -> Result<(), RingBufferError>;
 
-    fn commit(&self, index: i32);
+    /// Claim `length` bytes for a message to be encoded in place in `buffer`, returning where the
+    /// message starts. The claim must then be released with `commit` or `abort`.
+    fn try_claim(&self, msg_type_id: i32, length: i32) -> Result<ClaimIndex, RingBufferError>;
 
-    fn abort(&self, index: i32);
+    /// Claim `length` bytes for a message, returning a guard over the claimed region. The guard
+    /// aborts the claim when dropped without `commit`.
+    fn claim(&self, msg_type_id: i32, length: i32) -> Result<Claim<'_, Self>, RingBufferError> where Self: Sized {
+        let index = self.try_claim(msg_type_id, length)?;
+
+        Ok(Claim {
+            ring_buffer: self,
+            index: index.index(),
+            length,
+            released: false,
+        })
+    }
+
+    fn commit(&self, index: i32) {
+        if let Err(e) = self.try_commit(index) {
+            panic!("{}", e);
+        }
+    }
+
+    fn try_commit(&self, index: i32) -> Result<(), RingBufferError>;
+
+    fn abort(&self, index: i32) {
+        if let Err(e) = self.try_abort(index) {
+            panic!("{}", e);
+        }
+    }
+
+    fn try_abort(&self, index: i32) -> Result<(), RingBufferError>;
 
     fn read<F>(&self, func: F) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32);
 
     fn read0<F>(&self, func: F, message_count_limit: i32) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32);
 
+    /// Read up to `message_count_limit` messages, handing each to `func` as a `Message` view.
+    fn read_messages<F>(&self, mut func: F, message_count_limit: i32) -> i32 where F: FnMut(Message<'_>) {
+        self.read0(|msg_type_id, buffer, index, length| func(Message::new(msg_type_id, buffer, index, length)), message_count_limit)
+    }
+
+    /// Take the available messages one at a time, advancing the head once when the `Drain` is dropped.
+    fn drain(&self) -> Drain<'_>;
+
     fn controlled_read<F>(&self, func: F) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action;
 
     fn controlled_read0<F>(&self, func: F, message_count_limit: i32) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action;
@@ -42,6 +198,16 @@ pub trait RingBuffer {
 
     fn consumer_heartbeat_time(&self) -> i64;
 
+    /// Current time in ms on the clock given to `with_consumer_heartbeat`, or the system clock.
+    fn heartbeat_clock_time(&self) -> i64;
+
+    /// Whether the consumer has stamped its heartbeat within the last `timeout_ms`, see
+    /// `with_consumer_heartbeat`. A consumer which has never stamped it is not alive.
+    fn is_consumer_alive(&self, timeout_ms: i64) -> bool {
+        let heartbeat_time = self.consumer_heartbeat_time();
+        heartbeat_time != 0 && self.heartbeat_clock_time() - heartbeat_time <= timeout_ms
+    }
+
     fn producer_position(&self) -> i64;
 
     fn consumer_position(&self) -> i64;
@@ -50,3 +216,65 @@ pub trait RingBuffer {
 
     fn unblock(&self) -> bool;
 }
+
+/// Space claimed in a ring buffer by `RingBuffer::claim`.
+///
+/// Derefs to the claimed bytes only, so encoding cannot run into neighbouring records. The record
+/// is published by `commit`; dropping the claim without committing, e.g. when unwinding from a
+/// panic, aborts it so the consumer can move past it.
+pub struct Claim<'a, R: RingBuffer> {
+    ring_buffer: &'a R,
+    index: i32,
+    length: i32,
+    released: bool,
+}
+
+impl<R: RingBuffer> Claim<'_, R> {
+    /// Index in `RingBuffer::buffer` at which the claimed region begins.
+    pub fn index(&self) -> i32 {
+        self.index
+    }
+
+    /// Publish the record, failing if it was already released, e.g. padded over by `unblock`.
+    pub fn commit(mut self) -> Result<(), RingBufferError> {
+        self.released = true;
+        self.ring_buffer.try_commit(self.index)
+    }
+
+    pub fn abort(mut self) -> Result<(), RingBufferError> {
+        self.released = true;
+        self.ring_buffer.try_abort(self.index)
+    }
+}
+
+impl<R: RingBuffer> Deref for Claim<'_, R> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        let buffer = self.ring_buffer.buffer();
+        unsafe {
+            let ptr = buffer.byte_array().add((buffer.address_offset() + self.index) as usize);
+            slice::from_raw_parts(ptr, self.length as usize)
+        }
+    }
+}
+
+impl<R: RingBuffer> DerefMut for Claim<'_, R> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        let buffer = self.ring_buffer.buffer();
+        unsafe {
+            let ptr = buffer.byte_array().add((buffer.address_offset() + self.index) as usize);
+            slice::from_raw_parts_mut(ptr, self.length as usize)
+        }
+    }
+}
+
+impl<R: RingBuffer> Drop for Claim<'_, R> {
+    fn drop(&mut self) {
+        // may run while unwinding, where a second panic would abort the process, so an error
+        // from a record already released by `unblock` is ignored
+        if !self.released {
+            let _ = self.ring_buffer.try_abort(self.index);
+        }
+    }
+}
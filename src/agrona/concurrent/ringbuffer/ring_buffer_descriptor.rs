@@ -1,6 +1,12 @@
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
 use crate::bit_util::{CACHE_LINE_LENGTH, is_power_of_two_i32};
 
-pub const TAIL_POSITION_OFFSET: i32 = 0;
+// The trailer offsets follow Agrona's RingBufferDescriptor, with the aim of sharing a buffer with a
+// Java process; that has not been checked against Agrona itself yet, see tests/ring_buffer_layout.rs.
+// Each counter sits on its own pair of cache lines to avoid false sharing.
+
+/// Offset within the trailer for where the tail value is stored.
+pub const TAIL_POSITION_OFFSET: i32 = CACHE_LINE_LENGTH * 2;
 
 /// Offset within the trailer for where the head cache value is stored.
 pub const HEAD_CACHE_POSITION_OFFSET: i32 = TAIL_POSITION_OFFSET + (CACHE_LINE_LENGTH * 2);
@@ -17,20 +23,21 @@ pub const CONSUMER_HEARTBEAT_OFFSET: i32 = CORRELATION_COUNTER_OFFSET + (CACHE_L
 /// Total length of the trailer in bytes.
 pub const TRAILER_LENGTH: i32 = CONSUMER_HEARTBEAT_OFFSET + (CACHE_LINE_LENGTH * 2);
 
-pub fn check_capacity(capacity: i32, min_capacity: i32) -> i32 {
+pub fn check_capacity(capacity: i32, min_capacity: i32) -> Result<i32, RingBufferError> {
     let data_capacity = capacity - TRAILER_LENGTH;
     if !is_power_of_two_i32(data_capacity) {
-        panic!(
-            "capacity must be a positive power of 2 + TRAILER_LENGTH: capacity={}",
-            capacity
-        );
+        return Err(RingBufferError::InvalidCapacity(capacity));
     }
     if data_capacity < min_capacity {
-        panic!(
-            "insufficient capacity: minCapacity={}, capacity={}",
-            min_capacity + TRAILER_LENGTH,
-            capacity
-        );
+        return Err(RingBufferError::CapacityTooSmall {
+            min_capacity: min_capacity + TRAILER_LENGTH,
+            capacity,
+        });
     }
-    data_capacity
+    Ok(data_capacity)
+}
+
+/// Length of the buffer needed to hold a ring buffer with `capacity` bytes for messages.
+pub fn buffer_length(capacity: i32) -> i32 {
+    capacity + TRAILER_LENGTH
 }
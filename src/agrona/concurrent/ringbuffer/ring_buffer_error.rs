@@ -0,0 +1,43 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a ring buffer operation can fail without it being a bug in the ring buffer itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingBufferError {
+    /// Not enough free space for the message, the consumer needs to catch up.
+    InsufficientCapacity,
+    InvalidMessageLength(i32),
+    MessageTooLong { length: i32, max_msg_length: i32 },
+    BatchTooLong { length: i32, max_batch_length: i32 },
+    InvalidTypeId(i32),
+    /// The index passed to commit or abort is not one returned by `try_claim`.
+    InvalidIndex(i32),
+    ClaimAborted,
+    ClaimCommitted,
+    InvalidCapacity(i32),
+    CapacityTooSmall { min_capacity: i32, capacity: i32 },
+}
+
+impl fmt::Display for RingBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RingBufferError::InsufficientCapacity => write!(f, "insufficient capacity in ring buffer"),
+            RingBufferError::InvalidMessageLength(length) => write!(f, "Invalid message length={}", length),
+            RingBufferError::MessageTooLong { length, max_msg_length } =>
+                write!(f, "Encoded message exceeds max_msg_length={}, length={}", max_msg_length, length),
+            RingBufferError::BatchTooLong { length, max_batch_length } =>
+                write!(f, "Encoded batch exceeds max_batch_length={}, length={}", max_batch_length, length),
+            RingBufferError::InvalidTypeId(msg_type_id) =>
+                write!(f, "message type id must be greater than zero, msgTypeId={}", msg_type_id),
+            RingBufferError::InvalidIndex(index) => write!(f, "Invalid message index={}", index),
+            RingBufferError::ClaimAborted => write!(f, "claimed space previously aborted"),
+            RingBufferError::ClaimCommitted => write!(f, "claimed space previously committed"),
+            RingBufferError::InvalidCapacity(capacity) =>
+                write!(f, "capacity must be a positive power of 2 + TRAILER_LENGTH: capacity={}", capacity),
+            RingBufferError::CapacityTooSmall { min_capacity, capacity } =>
+                write!(f, "insufficient capacity: minCapacity={}, capacity={}", min_capacity, capacity),
+        }
+    }
+}
+
+impl Error for RingBufferError {}
@@ -0,0 +1,95 @@
+use crate::agrona::concurrent::status::atomic_counter::AtomicCounter;
+use crate::agrona::concurrent::status::counters_error::CountersError;
+use crate::agrona::concurrent::status::counters_manager::CountersManager;
+
+pub const MESSAGES_WRITTEN_TYPE_ID: i32 = 101;
+pub const BYTES_WRITTEN_TYPE_ID: i32 = 102;
+pub const CLAIM_FAILURES_TYPE_ID: i32 = 103;
+pub const PADDING_RECORDS_TYPE_ID: i32 = 104;
+pub const ABORTS_TYPE_ID: i32 = 105;
+pub const MAX_FILL_LEVEL_TYPE_ID: i32 = 106;
+
+const COUNTER_COUNT: i32 = 6;
+
+/// Counters maintained by a `OneToOneRingBuffer`, see `OneToOneRingBuffer::with_stats`.
+///
+/// Each counter has a single writer, either the producer or the consumer, so they are updated with
+/// ordered writes rather than atomic increments and can be read from any thread or process through
+/// a `CountersReader` without contending with the ring buffer. Labels are prefixed with the `name`
+/// given on creation and the type ids identify which statistic a counter holds. For that reason
+/// they are not available on a `ManyToOneRingBuffer`, whose producers would race on the counters.
+pub struct RingBufferStats {
+    messages_written: AtomicCounter,
+    bytes_written: AtomicCounter,
+    claim_failures: AtomicCounter,
+    padding_records: AtomicCounter,
+    aborts: AtomicCounter,
+    max_fill_level: AtomicCounter,
+}
+
+impl RingBufferStats {
+    pub fn new(counters_manager: &mut CountersManager, name: &str) -> Result<Self, CountersError> {
+        if counters_manager.available_counter_capacity() < COUNTER_COUNT {
+            return Err(CountersError::BufferFull { max_counter_id: counters_manager.max_counter_id() });
+        }
+        let mut new_counter = |label: &str, type_id: i32| {
+            counters_manager.new_counter_with_key(&format!("{}: {}", name, label), type_id, &[])
+        };
+
+        Ok(RingBufferStats {
+            messages_written: new_counter("messages written", MESSAGES_WRITTEN_TYPE_ID)?,
+            bytes_written: new_counter("bytes written", BYTES_WRITTEN_TYPE_ID)?,
+            claim_failures: new_counter("claim failures", CLAIM_FAILURES_TYPE_ID)?,
+            padding_records: new_counter("padding records", PADDING_RECORDS_TYPE_ID)?,
+            aborts: new_counter("aborts", ABORTS_TYPE_ID)?,
+            max_fill_level: new_counter("max fill level", MAX_FILL_LEVEL_TYPE_ID)?,
+        })
+    }
+
+    /// Messages written or committed by the producer.
+    pub fn messages_written(&self) -> &AtomicCounter {
+        &self.messages_written
+    }
+
+    /// Bytes of encoded messages written or committed, excluding record headers and alignment.
+    pub fn bytes_written(&self) -> &AtomicCounter {
+        &self.bytes_written
+    }
+
+    /// Writes and claims which failed with `RingBufferError::InsufficientCapacity`.
+    pub fn claim_failures(&self) -> &AtomicCounter {
+        &self.claim_failures
+    }
+
+    /// Padding records passed over by the consumer, at the end of the buffer or left by aborts.
+    pub fn padding_records(&self) -> &AtomicCounter {
+        &self.padding_records
+    }
+
+    pub fn aborts(&self) -> &AtomicCounter {
+        &self.aborts
+    }
+
+    /// Highest `size()` seen by the producer after a write.
+    pub fn max_fill_level(&self) -> &AtomicCounter {
+        &self.max_fill_level
+    }
+
+    pub(crate) fn on_written(&self, messages: i64, bytes: i64, size: i32) {
+        self.messages_written.get_and_add_ordered(messages);
+        self.bytes_written.get_and_add_ordered(bytes);
+        self.max_fill_level.propose_max_ordered(size as i64);
+    }
+
+    pub(crate) fn on_claim_failure(&self) {
+        self.claim_failures.increment_ordered();
+    }
+
+    pub(crate) fn on_padding(&self) {
+        self.padding_records.increment_ordered();
+    }
+
+    pub(crate) fn on_abort(&self) {
+        self.aborts.increment_ordered();
+    }
+}
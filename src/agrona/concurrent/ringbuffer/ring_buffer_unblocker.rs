@@ -0,0 +1,55 @@
+use crate::agrona::concurrent::epoch_clock::EpochClock;
+use crate::agrona::concurrent::ringbuffer::ring_buffer::RingBuffer;
+
+/// Consumer-side helper which unblocks a ring buffer when a producer has claimed space and not
+/// committed it within the stall timeout, e.g. because it died between `try_claim` and `commit`.
+///
+/// Call `poll` once per duty cycle of the consumer. Each poll stamps the consumer heartbeat with
+/// the time from the epoch clock, and when the consumer position has not moved for longer than the timeout while the producer
+/// position is ahead of it, `RingBuffer::unblock` is called.
+pub struct RingBufferUnblocker<'a, R: RingBuffer, C: EpochClock> {
+    ring_buffer: &'a R,
+    epoch_clock: C,
+    stall_timeout_ms: i64,
+    last_consumer_position: i64,
+    last_progress_time_ms: i64,
+}
+
+impl<'a, R: RingBuffer, C: EpochClock> RingBufferUnblocker<'a, R, C> {
+    pub fn new(ring_buffer: &'a R, stall_timeout_ms: i64, epoch_clock: C) -> Self {
+        let now_ms = epoch_clock.time();
+        RingBufferUnblocker {
+            ring_buffer,
+            epoch_clock,
+            stall_timeout_ms,
+            last_consumer_position: ring_buffer.consumer_position(),
+            last_progress_time_ms: now_ms,
+        }
+    }
+
+    pub fn stall_timeout_ms(&self) -> i64 {
+        self.stall_timeout_ms
+    }
+
+    /// Check the ring buffer for a stalled producer, returning true if it was unblocked.
+    pub fn poll(&mut self) -> bool {
+        let ring_buffer = self.ring_buffer;
+        let now_ms = self.epoch_clock.time();
+        ring_buffer.put_consumer_heartbeat_time(now_ms);
+
+        let consumer_position = ring_buffer.consumer_position();
+        if consumer_position != self.last_consumer_position
+            || ring_buffer.producer_position() <= consumer_position {
+            self.last_consumer_position = consumer_position;
+            self.last_progress_time_ms = now_ms;
+            return false;
+        }
+
+        if now_ms - self.last_progress_time_ms > self.stall_timeout_ms && ring_buffer.unblock() {
+            self.last_progress_time_ms = now_ms;
+            return true;
+        }
+
+        false
+    }
+}
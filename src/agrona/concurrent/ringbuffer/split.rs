@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use crate::agrona::concurrent::controlled_message_handler::Action;
+use crate::agrona::concurrent::ringbuffer::drain::Drain;
+use crate::agrona::concurrent::ringbuffer::message::Message;
+use crate::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use crate::agrona::concurrent::ringbuffer::ring_buffer::{Claim, RingBuffer};
+use crate::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+
+/// The producing half of a split `OneToOneRingBuffer`.
+///
+/// There is exactly one `Producer` per ring buffer: it can be sent to another thread but not
+/// cloned, and every operation takes `&mut self`.
+pub struct Producer {
+    ring_buffer: Arc<OneToOneRingBuffer>,
+}
+
+/// The consuming half of a split `OneToOneRingBuffer`, see `Producer`.
+pub struct Consumer {
+    ring_buffer: Arc<OneToOneRingBuffer>,
+}
+
+impl OneToOneRingBuffer {
+    /// Split the ring buffer into its single producer and single consumer.
+    pub fn split(self) -> (Producer, Consumer) {
+        let ring_buffer = Arc::new(self);
+        let producer = Producer { ring_buffer: Arc::clone(&ring_buffer) };
+        let consumer = Consumer { ring_buffer };
+        (producer, consumer)
+    }
+}
+
+impl Producer {
+    pub fn capacity(&self) -> i32 {
+        self.ring_buffer.capacity()
+    }
+
+    pub fn max_msg_length(&self) -> i32 {
+        self.ring_buffer.max_msg_length()
+    }
+
+    pub fn write(&mut self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> bool {
+        self.ring_buffer.write(msg_type_id, src_buffer, offset, length)
+    }
+
+    pub fn try_write(&mut self, msg_type_id: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) -> Result<(), RingBufferError> {
+        self.ring_buffer.try_write(msg_type_id, src_buffer, offset, length)
+    }
+
+    pub fn write_vectored(&mut self, msg_type_id: i32, srcs: &[&[u8]]) -> bool {
+        self.ring_buffer.write_vectored(msg_type_id, srcs)
+    }
+
+    pub fn try_write_vectored(&mut self, msg_type_id: i32, srcs: &[&[u8]]) -> Result<(), RingBufferError> {
+        self.ring_buffer.try_write_vectored(msg_type_id, srcs)
+    }
+
+    pub fn write_batch(&mut self, messages: &[(i32, &[u8])]) -> bool {
+        self.ring_buffer.write_batch(messages)
+    }
+
+    pub fn try_write_batch(&mut self, messages: &[(i32, &[u8])]) -> Result<(), RingBufferError> {
+        self.ring_buffer.try_write_batch(messages)
+    }
+
+    /// Claim `length` bytes for a message, failing with `RingBufferError::InsufficientCapacity` if
+    /// the buffer is full. The claim is aborted if dropped without being committed.
+    pub fn try_claim(&mut self, msg_type_id: i32, length: i32) -> Result<Claim<'_, OneToOneRingBuffer>, RingBufferError> {
+        self.ring_buffer.claim(msg_type_id, length)
+    }
+
+    pub fn consumer_heartbeat_time(&self) -> i64 {
+        self.ring_buffer.consumer_heartbeat_time()
+    }
+
+    pub fn is_consumer_alive(&self, timeout_ms: i64) -> bool {
+        self.ring_buffer.is_consumer_alive(timeout_ms)
+    }
+
+    pub fn producer_position(&self) -> i64 {
+        self.ring_buffer.producer_position()
+    }
+
+    pub fn size(&self) -> i32 {
+        self.ring_buffer.size()
+    }
+}
+
+impl Consumer {
+    pub fn capacity(&self) -> i32 {
+        self.ring_buffer.capacity()
+    }
+
+    pub fn read<F>(&mut self, func: F) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        self.ring_buffer.read(func)
+    }
+
+    pub fn read0<F>(&mut self, func: F, message_count_limit: i32) -> i32 where F: FnMut(i32, &UnsafeBuffer, i32, i32) {
+        self.ring_buffer.read0(func, message_count_limit)
+    }
+
+    pub fn read_messages<F>(&mut self, func: F, message_count_limit: i32) -> i32 where F: FnMut(Message<'_>) {
+        self.ring_buffer.read_messages(func, message_count_limit)
+    }
+
+    pub fn drain(&mut self) -> Drain<'_> {
+        self.ring_buffer.drain()
+    }
+
+    pub fn controlled_read<F>(&mut self, func: F) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
+        self.ring_buffer.controlled_read(func)
+    }
+
+    pub fn controlled_read0<F>(&mut self, func: F, message_count_limit: i32) -> i32 where F: Fn(i32, &UnsafeBuffer, i32, i32) -> Action {
+        self.ring_buffer.controlled_read0(func, message_count_limit)
+    }
+
+    pub fn put_consumer_heartbeat_time(&mut self, time: i64) {
+        self.ring_buffer.put_consumer_heartbeat_time(time)
+    }
+
+    pub fn consumer_position(&self) -> i64 {
+        self.ring_buffer.consumer_position()
+    }
+
+    pub fn size(&self) -> i32 {
+        self.ring_buffer.size()
+    }
+
+    pub fn unblock(&mut self) -> bool {
+        self.ring_buffer.unblock()
+    }
+}
@@ -0,0 +1,41 @@
+use std::thread;
+use std::time::Duration;
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+
+pub const ALIAS: &str = "sleep-ns";
+
+pub const DEFAULT_SLEEP_PERIOD_NS: u64 = 1000;
+
+/// Sleeps for a fixed period when idle. Cheapest on CPU, but wakes up late by at least the period.
+#[derive(Debug, Clone, Copy)]
+pub struct SleepingIdleStrategy {
+    sleep_period_ns: u64,
+}
+
+impl SleepingIdleStrategy {
+    pub fn new(sleep_period_ns: u64) -> Self {
+        SleepingIdleStrategy { sleep_period_ns }
+    }
+
+    pub fn sleep_period_ns(&self) -> u64 {
+        self.sleep_period_ns
+    }
+}
+
+impl Default for SleepingIdleStrategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLEEP_PERIOD_NS)
+    }
+}
+
+impl IdleStrategy for SleepingIdleStrategy {
+    fn idle0(&mut self) {
+        thread::sleep(Duration::from_nanos(self.sleep_period_ns));
+    }
+
+    fn reset(&mut self) {}
+
+    fn alias(&self) -> &'static str {
+        ALIAS
+    }
+}
@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex};
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::status::counters_error::CountersError;
+use crate::agrona::concurrent::status::counters_manager::{lock, FreeList};
+use crate::agrona::concurrent::status::counters_reader::{counter_offset, label_at, meta_data_offset, CountersReader};
+use crate::agrona::direct_buffer::DirectBuffer;
+
+/// Atomic counter that is backed by a values buffer, which can be read across threads and
+/// processes with a `CountersReader`.
+///
+/// The `_ordered` and `_weak` variants are cheaper when the counter has a single writer. A counter
+/// made by a `CountersManager` frees its id when closed or dropped.
+pub struct AtomicCounter {
+    counters: Arc<CountersReader>,
+    id: i32,
+    offset: i32,
+    free_list: Option<Arc<Mutex<FreeList>>>,
+}
+
+impl AtomicCounter {
+    pub fn new(counters: Arc<CountersReader>, counter_id: i32) -> Result<Self, CountersError> {
+        counters.validate_counter_id(counter_id)?;
+        Ok(AtomicCounter {
+            counters,
+            id: counter_id,
+            offset: counter_offset(counter_id),
+            free_list: None,
+        })
+    }
+
+    pub(crate) fn allocated(counters: Arc<CountersReader>, counter_id: i32, free_list: Arc<Mutex<FreeList>>) -> Self {
+        AtomicCounter {
+            counters,
+            id: counter_id,
+            offset: counter_offset(counter_id),
+            free_list: Some(free_list),
+        }
+    }
+
+    /// Free the counter in the `CountersManager` which allocated it, if any. Only the first call
+    /// has an effect.
+    pub fn close(&mut self) -> Result<(), CountersError> {
+        match self.free_list.take() {
+            Some(free_list) => lock(&free_list).free(&self.counters, self.id),
+            None => Ok(()),
+        }
+    }
+
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn label(&self) -> String {
+        label_at(self.counters.meta_data_buffer(), meta_data_offset(self.id))
+    }
+
+    /// Perform an atomic increment that will not lose updates across threads.
+    ///
+    /// @return the previous value of the counter
+    pub fn increment(&self) -> i64 {
+        self.counters.values_buffer().get_and_add_long(self.offset, 1)
+    }
+
+    /// Perform an atomic increment that is not safe across threads.
+    ///
+    /// @return the previous value of the counter
+    pub fn increment_ordered(&self) -> i64 {
+        let buffer = self.counters.values_buffer();
+        let current_value = buffer.get_long(self.offset);
+        buffer.add_long_ordered(self.offset, 1);
+        current_value
+    }
+
+    pub fn decrement(&self) -> i64 {
+        self.counters.values_buffer().get_and_add_long(self.offset, -1)
+    }
+
+    pub fn decrement_ordered(&self) -> i64 {
+        let buffer = self.counters.values_buffer();
+        let current_value = buffer.get_long(self.offset);
+        buffer.add_long_ordered(self.offset, -1);
+        current_value
+    }
+
+    pub fn set(&self, value: i64) {
+        self.counters.values_buffer().put_long_volatile(self.offset, value);
+    }
+
+    pub fn set_ordered(&self, value: i64) {
+        self.counters.values_buffer().put_long_ordered(self.offset, value);
+    }
+
+    pub fn set_weak(&self, value: i64) {
+        self.counters.values_buffer().put_long(self.offset, value);
+    }
+
+    pub fn get_and_add(&self, delta: i64) -> i64 {
+        self.counters.values_buffer().get_and_add_long(self.offset, delta)
+    }
+
+    pub fn get_and_add_ordered(&self, delta: i64) -> i64 {
+        let buffer = self.counters.values_buffer();
+        let current_value = buffer.get_long(self.offset);
+        buffer.add_long_ordered(self.offset, delta);
+        current_value
+    }
+
+    pub fn compare_and_set(&self, expected_value: i64, update_value: i64) -> bool {
+        self.counters.values_buffer().compare_and_set_long(self.offset, expected_value, update_value)
+    }
+
+    pub fn get(&self) -> i64 {
+        self.counters.values_buffer().get_long_volatile(self.offset)
+    }
+
+    pub fn get_weak(&self) -> i64 {
+        self.counters.values_buffer().get_long(self.offset)
+    }
+
+    /// Set the value to `proposed_value` if greater than the current value, not safe across threads.
+    ///
+    /// @return true if updated otherwise false.
+    pub fn propose_max(&self, proposed_value: i64) -> bool {
+        let buffer = self.counters.values_buffer();
+        if buffer.get_long(self.offset) < proposed_value {
+            buffer.put_long(self.offset, proposed_value);
+            return true;
+        }
+        false
+    }
+
+    pub fn propose_max_ordered(&self, proposed_value: i64) -> bool {
+        let buffer = self.counters.values_buffer();
+        if buffer.get_long(self.offset) < proposed_value {
+            buffer.put_long_ordered(self.offset, proposed_value);
+            return true;
+        }
+        false
+    }
+}
+
+impl Drop for AtomicCounter {
+    fn drop(&mut self) {
+        // the id may already have been freed through CountersManager::free
+        let _ = self.close();
+    }
+}
@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a counter cannot be allocated, freed or accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CountersError {
+    /// Every counter in the values buffer is allocated or waiting out its reuse timeout.
+    BufferFull { max_counter_id: i32 },
+    KeyTooLong { length: usize, max_key_length: i32 },
+    CounterNotAllocated(i32),
+    InvalidCounterId { counter_id: i32, max_counter_id: i32 },
+}
+
+impl fmt::Display for CountersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CountersError::BufferFull { max_counter_id } =>
+                write!(f, "unable to allocate counter, buffer is full: maxCounterId={}", max_counter_id),
+            CountersError::KeyTooLong { length, max_key_length } =>
+                write!(f, "key is too long: length={}, maxKeyLength={}", length, max_key_length),
+            CountersError::CounterNotAllocated(counter_id) => write!(f, "counter not allocated: counterId={}", counter_id),
+            CountersError::InvalidCounterId { counter_id, max_counter_id } =>
+                write!(f, "counter id {} out of range: 0 - maxCounterId={}", counter_id, max_counter_id),
+        }
+    }
+}
+
+impl Error for CountersError {}
@@ -0,0 +1,181 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use crate::agrona::concurrent::status::atomic_counter::AtomicCounter;
+use crate::agrona::concurrent::status::counters_error::CountersError;
+use crate::agrona::concurrent::status::counters_reader::*;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::SIZE_OF_INT;
+
+/// Manages the allocation and freeing of counters that are normally stored in memory-mapped
+/// files, so they can be read by other processes with a `CountersReader`.
+///
+/// Freed counters are reused once `free_to_reuse_timeout_ms` has passed, giving readers time to
+/// notice a counter has gone before its id is taken by another.
+pub struct CountersManager {
+    reader: Arc<CountersReader>,
+    id_high_water_mark: i32,
+    free_list: Arc<Mutex<FreeList>>,
+}
+
+/// Ids of freed counters, shared with the `AtomicCounter`s a manager creates so they can free
+/// their id when closed.
+pub(crate) struct FreeList {
+    counter_ids: Vec<i32>,
+    epoch_clock: Box<dyn EpochClock + Send>,
+    free_to_reuse_timeout_ms: i64,
+}
+
+impl FreeList {
+    pub(crate) fn free(&mut self, reader: &CountersReader, counter_id: i32) -> Result<(), CountersError> {
+        if reader.get_counter_state(counter_id)? != RECORD_ALLOCATED {
+            return Err(CountersError::CounterNotAllocated(counter_id));
+        }
+        let record_offset = meta_data_offset(counter_id);
+        let buffer = reader.meta_data_buffer();
+
+        buffer.put_long(record_offset + FREE_FOR_REUSE_DEADLINE_OFFSET, self.epoch_clock.time() + self.free_to_reuse_timeout_ms);
+        buffer.put_int_ordered(record_offset, RECORD_RECLAIMED);
+        self.counter_ids.push(counter_id);
+        Ok(())
+    }
+
+    fn is_reusable(reader: &CountersReader, counter_id: i32, now_ms: i64) -> bool {
+        reader.get_free_for_reuse_deadline(counter_id).is_ok_and(|deadline| now_ms >= deadline)
+    }
+}
+
+// a panic while holding the lock cannot leave the list half updated, so poisoning is ignored
+pub(crate) fn lock(free_list: &Mutex<FreeList>) -> MutexGuard<'_, FreeList> {
+    free_list.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl CountersManager {
+    pub fn new(meta_data_buffer: UnsafeBuffer, values_buffer: UnsafeBuffer) -> Self {
+        Self::with_free_to_reuse_timeout(meta_data_buffer, values_buffer, SystemEpochClock, 0)
+    }
+
+    /// @param epoch_clock              giving the time in ms since epoch for reuse deadlines.
+    /// @param free_to_reuse_timeout_ms before a freed counter can be allocated again.
+    pub fn with_free_to_reuse_timeout<C>(meta_data_buffer: UnsafeBuffer, values_buffer: UnsafeBuffer, epoch_clock: C, free_to_reuse_timeout_ms: i64) -> Self
+    where C: EpochClock + Send + 'static {
+        CountersManager {
+            reader: Arc::new(CountersReader::new(meta_data_buffer, values_buffer)),
+            id_high_water_mark: -1,
+            free_list: Arc::new(Mutex::new(FreeList {
+                counter_ids: Vec::new(),
+                epoch_clock: Box::new(epoch_clock),
+                free_to_reuse_timeout_ms,
+            })),
+        }
+    }
+
+    /// Reader over the same buffers which can be shared with other threads, e.g. by counters.
+    pub fn reader(&self) -> Arc<CountersReader> {
+        Arc::clone(&self.reader)
+    }
+
+    /// Counters that could be allocated now, leaving out freed ids still within their reuse timeout.
+    pub fn available_counter_capacity(&self) -> i32 {
+        let free_list = lock(&self.free_list);
+        let now_ms = free_list.epoch_clock.time();
+        let reusable = free_list.counter_ids.iter().filter(|&&counter_id| FreeList::is_reusable(&self.reader, counter_id, now_ms)).count();
+        self.reader.max_counter_id() - self.id_high_water_mark + reusable as i32
+    }
+
+    pub fn allocate(&mut self, label: &str) -> Result<i32, CountersError> {
+        self.allocate_with_key(label, DEFAULT_TYPE_ID, &[])
+    }
+
+    /// Allocate a counter with a type id and a key of up to `MAX_KEY_LENGTH` bytes.
+    pub fn allocate_with_key(&mut self, label: &str, type_id: i32, key: &[u8]) -> Result<i32, CountersError> {
+        if key.len() > MAX_KEY_LENGTH as usize {
+            return Err(CountersError::KeyTooLong { length: key.len(), max_key_length: MAX_KEY_LENGTH });
+        }
+        let counter_id = self.next_counter_id()?;
+        let record_offset = meta_data_offset(counter_id);
+        let buffer = self.reader.meta_data_buffer();
+
+        buffer.put_int(record_offset + TYPE_ID_OFFSET, type_id);
+        buffer.put_long(record_offset + FREE_FOR_REUSE_DEADLINE_OFFSET, NOT_FREE_TO_REUSE);
+        buffer.set_memory(record_offset + KEY_OFFSET, MAX_KEY_LENGTH, 0);
+        buffer.put_bytes(record_offset + KEY_OFFSET, key);
+        Self::put_label(buffer, record_offset, label);
+        buffer.put_int_ordered(record_offset, RECORD_ALLOCATED);
+
+        Ok(counter_id)
+    }
+
+    pub fn new_counter(&mut self, label: &str) -> Result<AtomicCounter, CountersError> {
+        let counter_id = self.allocate(label)?;
+        Ok(AtomicCounter::allocated(self.reader(), counter_id, Arc::clone(&self.free_list)))
+    }
+
+    pub fn new_counter_with_key(&mut self, label: &str, type_id: i32, key: &[u8]) -> Result<AtomicCounter, CountersError> {
+        let counter_id = self.allocate_with_key(label, type_id, key)?;
+        Ok(AtomicCounter::allocated(self.reader(), counter_id, Arc::clone(&self.free_list)))
+    }
+
+    /// Free a counter so its id can be reused after the reuse timeout.
+    pub fn free(&mut self, counter_id: i32) -> Result<(), CountersError> {
+        lock(&self.free_list).free(&self.reader, counter_id)
+    }
+
+    pub fn set_counter_value(&self, counter_id: i32, value: i64) -> Result<(), CountersError> {
+        self.reader.validate_counter_id(counter_id)?;
+        self.reader.values_buffer().put_long_ordered(counter_offset(counter_id), value);
+        Ok(())
+    }
+
+    pub fn set_counter_registration_id(&self, counter_id: i32, registration_id: i64) -> Result<(), CountersError> {
+        self.reader.validate_counter_id(counter_id)?;
+        self.reader.values_buffer().put_long_ordered(counter_offset(counter_id) + REGISTRATION_ID_OFFSET, registration_id);
+        Ok(())
+    }
+
+    pub fn set_counter_owner_id(&self, counter_id: i32, owner_id: i64) -> Result<(), CountersError> {
+        self.reader.validate_counter_id(counter_id)?;
+        self.reader.values_buffer().put_long_ordered(counter_offset(counter_id) + OWNER_ID_OFFSET, owner_id);
+        Ok(())
+    }
+
+    fn next_counter_id(&mut self) -> Result<i32, CountersError> {
+        let reader = &self.reader;
+        let mut free_list = lock(&self.free_list);
+        let now_ms = free_list.epoch_clock.time();
+        if let Some(i) = free_list.counter_ids.iter().position(|&counter_id| FreeList::is_reusable(reader, counter_id, now_ms)) {
+            let counter_id = free_list.counter_ids.remove(i);
+            let values_buffer = reader.values_buffer();
+            let offset = counter_offset(counter_id);
+            values_buffer.put_long_ordered(offset + REGISTRATION_ID_OFFSET, DEFAULT_REGISTRATION_ID);
+            values_buffer.put_long(offset + OWNER_ID_OFFSET, DEFAULT_OWNER_ID);
+            values_buffer.put_long_ordered(offset, 0);
+            return Ok(counter_id);
+        }
+
+        if self.id_high_water_mark == reader.max_counter_id() {
+            return Err(CountersError::BufferFull { max_counter_id: reader.max_counter_id() });
+        }
+        self.id_high_water_mark += 1;
+        Ok(self.id_high_water_mark)
+    }
+
+    fn put_label(buffer: &mut UnsafeBuffer, record_offset: i32, label: &str) {
+        let mut length = label.len().min(MAX_LABEL_LENGTH as usize);
+        while !label.is_char_boundary(length) {
+            length -= 1;
+        }
+        buffer.put_bytes(record_offset + LABEL_OFFSET + SIZE_OF_INT, &label.as_bytes()[..length]);
+        buffer.put_int(record_offset + LABEL_OFFSET, length as i32);
+    }
+}
+
+impl Deref for CountersManager {
+    type Target = CountersReader;
+
+    fn deref(&self) -> &CountersReader {
+        &self.reader
+    }
+}
@@ -0,0 +1,187 @@
+use std::cell::UnsafeCell;
+use std::slice;
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::status::counters_error::CountersError;
+use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use crate::agrona::direct_buffer::DirectBuffer;
+use crate::bit_util::{CACHE_LINE_LENGTH, SIZE_OF_INT, SIZE_OF_LONG};
+
+// Layout of the values buffer, one record of COUNTER_LENGTH per counter:
+//
+//   0: value
+//   8: registration id
+//  16: owner id
+//      padding to 2 cache lines so counters written by different threads do not false share
+//
+// Layout of the metadata buffer, one record of METADATA_LENGTH per counter:
+//
+//   0: record state, RECORD_UNUSED, RECORD_ALLOCATED or RECORD_RECLAIMED
+//   4: type id
+//   8: free for reuse deadline in ms since epoch
+//  16: key of MAX_KEY_LENGTH bytes
+// 128: label length followed by MAX_LABEL_LENGTH bytes of label
+
+pub const NULL_COUNTER_ID: i32 = -1;
+pub const RECORD_UNUSED: i32 = 0;
+pub const RECORD_ALLOCATED: i32 = 1;
+pub const RECORD_RECLAIMED: i32 = -1;
+pub const DEFAULT_TYPE_ID: i32 = 0;
+pub const DEFAULT_REGISTRATION_ID: i64 = 0;
+pub const DEFAULT_OWNER_ID: i64 = 0;
+pub const NOT_FREE_TO_REUSE: i64 = i64::MAX;
+
+pub const REGISTRATION_ID_OFFSET: i32 = SIZE_OF_LONG;
+pub const OWNER_ID_OFFSET: i32 = REGISTRATION_ID_OFFSET + SIZE_OF_LONG;
+pub const COUNTER_LENGTH: i32 = CACHE_LINE_LENGTH * 2;
+
+pub const TYPE_ID_OFFSET: i32 = SIZE_OF_INT;
+pub const FREE_FOR_REUSE_DEADLINE_OFFSET: i32 = TYPE_ID_OFFSET + SIZE_OF_INT;
+pub const KEY_OFFSET: i32 = FREE_FOR_REUSE_DEADLINE_OFFSET + SIZE_OF_LONG;
+pub const LABEL_OFFSET: i32 = CACHE_LINE_LENGTH * 2;
+pub const MAX_KEY_LENGTH: i32 = LABEL_OFFSET - KEY_OFFSET;
+pub const MAX_LABEL_LENGTH: i32 = CACHE_LINE_LENGTH * 6 - SIZE_OF_INT;
+pub const METADATA_LENGTH: i32 = LABEL_OFFSET + SIZE_OF_INT + MAX_LABEL_LENGTH;
+
+/// Reads the counters in a values buffer and their metadata, e.g. from another process mapping
+/// the buffers written by a `CountersManager`.
+pub struct CountersReader {
+    max_counter_id: i32,
+    meta_data_buffer: UnsafeCell<UnsafeBuffer>,
+    values_buffer: UnsafeCell<UnsafeBuffer>,
+}
+
+unsafe impl Send for CountersReader {}
+
+unsafe impl Sync for CountersReader {}
+
+pub fn counter_offset(counter_id: i32) -> i32 {
+    counter_id * COUNTER_LENGTH
+}
+
+pub fn meta_data_offset(counter_id: i32) -> i32 {
+    counter_id * METADATA_LENGTH
+}
+
+fn bytes(buffer: &UnsafeBuffer, index: i32, length: i32) -> &[u8] {
+    unsafe {
+        slice::from_raw_parts(buffer.byte_array().add((buffer.address_offset() + index) as usize), length as usize)
+    }
+}
+
+pub(crate) fn label_at(meta_data_buffer: &UnsafeBuffer, record_offset: i32) -> String {
+    let label_offset = record_offset + LABEL_OFFSET;
+    let length = meta_data_buffer.get_int(label_offset).clamp(0, MAX_LABEL_LENGTH);
+    String::from_utf8_lossy(bytes(meta_data_buffer, label_offset + SIZE_OF_INT, length)).into_owned()
+}
+
+impl CountersReader {
+    pub fn new(meta_data_buffer: UnsafeBuffer, values_buffer: UnsafeBuffer) -> Self {
+        let max_counter_id = values_buffer.capacity() / COUNTER_LENGTH - 1;
+        if meta_data_buffer.capacity() < (max_counter_id + 1) * METADATA_LENGTH {
+            panic!("metadata buffer not sufficient for number of counters: metaDataBuffer.capacity={}, valuesBuffer.capacity={}",
+                   meta_data_buffer.capacity(), values_buffer.capacity());
+        }
+
+        CountersReader {
+            max_counter_id,
+            meta_data_buffer: UnsafeCell::new(meta_data_buffer),
+            values_buffer: UnsafeCell::new(values_buffer),
+        }
+    }
+
+    pub fn max_counter_id(&self) -> i32 {
+        self.max_counter_id
+    }
+
+    // the buffers are shared memory, writes go through the ordered and atomic accessors
+    #[allow(clippy::mut_from_ref)]
+    pub fn meta_data_buffer(&self) -> &mut UnsafeBuffer {
+        unsafe {
+            &mut *self.meta_data_buffer.get()
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn values_buffer(&self) -> &mut UnsafeBuffer {
+        unsafe {
+            &mut *self.values_buffer.get()
+        }
+    }
+
+    /// Check `counter_id` lies within the buffers, as the accessors below do before touching them.
+    pub fn validate_counter_id(&self, counter_id: i32) -> Result<(), CountersError> {
+        if counter_id < 0 || counter_id > self.max_counter_id {
+            return Err(CountersError::InvalidCounterId { counter_id, max_counter_id: self.max_counter_id });
+        }
+        Ok(())
+    }
+
+    pub fn get_counter_value(&self, counter_id: i32) -> Result<i64, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.values_buffer().get_long_volatile(counter_offset(counter_id)))
+    }
+
+    pub fn get_counter_registration_id(&self, counter_id: i32) -> Result<i64, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.values_buffer().get_long_volatile(counter_offset(counter_id) + REGISTRATION_ID_OFFSET))
+    }
+
+    pub fn get_counter_owner_id(&self, counter_id: i32) -> Result<i64, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.values_buffer().get_long_volatile(counter_offset(counter_id) + OWNER_ID_OFFSET))
+    }
+
+    pub fn get_counter_state(&self, counter_id: i32) -> Result<i32, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.meta_data_buffer().get_int_volatile(meta_data_offset(counter_id)))
+    }
+
+    pub fn get_counter_type_id(&self, counter_id: i32) -> Result<i32, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.meta_data_buffer().get_int(meta_data_offset(counter_id) + TYPE_ID_OFFSET))
+    }
+
+    pub fn get_free_for_reuse_deadline(&self, counter_id: i32) -> Result<i64, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(self.meta_data_buffer().get_long_volatile(meta_data_offset(counter_id) + FREE_FOR_REUSE_DEADLINE_OFFSET))
+    }
+
+    pub fn get_counter_key(&self, counter_id: i32) -> Result<&[u8], CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(bytes(self.meta_data_buffer(), meta_data_offset(counter_id) + KEY_OFFSET, MAX_KEY_LENGTH))
+    }
+
+    pub fn get_counter_label(&self, counter_id: i32) -> Result<String, CountersError> {
+        self.validate_counter_id(counter_id)?;
+        Ok(label_at(self.meta_data_buffer(), meta_data_offset(counter_id)))
+    }
+
+    /// Iterate over the allocated counters with their id, type id, key and label.
+    pub fn for_each<F>(&self, mut func: F) where F: FnMut(i32, i32, &[u8], &str) {
+        let buffer: &UnsafeBuffer = self.meta_data_buffer();
+        self.for_each_allocated(|counter_id| {
+            let record_offset = meta_data_offset(counter_id);
+            let label = label_at(buffer, record_offset);
+            func(counter_id, buffer.get_int(record_offset + TYPE_ID_OFFSET), bytes(buffer, record_offset + KEY_OFFSET, MAX_KEY_LENGTH), &label);
+        });
+    }
+
+    /// Iterate over the allocated counters with their value, id and label.
+    pub fn for_each_value<F>(&self, mut func: F) where F: FnMut(i64, i32, &str) {
+        self.for_each_allocated(|counter_id| {
+            let label = label_at(self.meta_data_buffer(), meta_data_offset(counter_id));
+            func(self.values_buffer().get_long_volatile(counter_offset(counter_id)), counter_id, &label);
+        });
+    }
+
+    fn for_each_allocated<F>(&self, mut func: F) where F: FnMut(i32) {
+        let buffer = self.meta_data_buffer();
+        for counter_id in 0..=self.max_counter_id {
+            match buffer.get_int_volatile(meta_data_offset(counter_id)) {
+                RECORD_ALLOCATED => func(counter_id),
+                RECORD_UNUSED => break,
+                _ => {}
+            }
+        }
+    }
+}
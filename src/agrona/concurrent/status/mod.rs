@@ -0,0 +1,6 @@
+pub mod counters_reader;
+pub mod counters_manager;
+pub mod counters_error;
+pub mod atomic_counter;
+pub mod position;
+pub mod unsafe_buffer_position;
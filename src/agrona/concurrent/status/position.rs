@@ -0,0 +1,22 @@
+/// Reports on how far through a buffer some component has progressed, e.g. a consumer's position
+/// in a stream, so that others can track it.
+pub trait Position {
+    fn id(&self) -> i32;
+
+    fn get(&self) -> i64;
+
+    fn get_volatile(&self) -> i64;
+
+    fn set(&self, value: i64);
+
+    fn set_ordered(&self, value: i64);
+
+    fn set_volatile(&self, value: i64);
+
+    /// Set the position to `proposed_value` if greater than the current value.
+    ///
+    /// @return true if updated otherwise false.
+    fn propose_max(&self, proposed_value: i64) -> bool;
+
+    fn propose_max_ordered(&self, proposed_value: i64) -> bool;
+}
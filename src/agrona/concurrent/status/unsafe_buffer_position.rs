@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use crate::agrona::concurrent::status::counters_error::CountersError;
+use crate::agrona::concurrent::status::counters_reader::{counter_offset, CountersReader};
+use crate::agrona::concurrent::status::position::Position;
+use crate::agrona::direct_buffer::DirectBuffer;
+
+/// `Position` stored in a counter of a values buffer.
+pub struct UnsafeBufferPosition {
+    counters: Arc<CountersReader>,
+    counter_id: i32,
+    offset: i32,
+}
+
+impl UnsafeBufferPosition {
+    pub fn new(counters: Arc<CountersReader>, counter_id: i32) -> Result<Self, CountersError> {
+        counters.validate_counter_id(counter_id)?;
+        Ok(UnsafeBufferPosition {
+            counters,
+            counter_id,
+            offset: counter_offset(counter_id),
+        })
+    }
+}
+
+impl Position for UnsafeBufferPosition {
+    fn id(&self) -> i32 {
+        self.counter_id
+    }
+
+    fn get(&self) -> i64 {
+        self.counters.values_buffer().get_long(self.offset)
+    }
+
+    fn get_volatile(&self) -> i64 {
+        self.counters.values_buffer().get_long_volatile(self.offset)
+    }
+
+    fn set(&self, value: i64) {
+        self.counters.values_buffer().put_long(self.offset, value);
+    }
+
+    fn set_ordered(&self, value: i64) {
+        self.counters.values_buffer().put_long_ordered(self.offset, value);
+    }
+
+    fn set_volatile(&self, value: i64) {
+        self.counters.values_buffer().put_long_volatile(self.offset, value);
+    }
+
+    fn propose_max(&self, proposed_value: i64) -> bool {
+        let buffer = self.counters.values_buffer();
+        if buffer.get_long(self.offset) < proposed_value {
+            buffer.put_long(self.offset, proposed_value);
+            return true;
+        }
+        false
+    }
+
+    fn propose_max_ordered(&self, proposed_value: i64) -> bool {
+        let buffer = self.counters.values_buffer();
+        if buffer.get_long(self.offset) < proposed_value {
+            buffer.put_long_ordered(self.offset, proposed_value);
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use crate::agrona::concurrent::epoch_clock::EpochClock;
+use crate::agrona::concurrent::nano_clock::NanoClock;
+
+/// A clock for tests which only moves when advanced. It is both an `EpochClock` and a
+/// `NanoClock`, reading the same time in milliseconds and nanoseconds.
+#[derive(Debug, Default)]
+pub struct TestClock {
+    nano_time: AtomicI64,
+}
+
+impl TestClock {
+    pub fn new(time_ms: i64) -> Self {
+        TestClock { nano_time: AtomicI64::new(time_ms * 1_000_000) }
+    }
+
+    pub fn set_time(&self, time_ms: i64) {
+        self.nano_time.store(time_ms * 1_000_000, Ordering::Release);
+    }
+
+    pub fn advance_ms(&self, delta_ms: i64) {
+        self.advance_ns(delta_ms * 1_000_000);
+    }
+
+    pub fn advance_ns(&self, delta_ns: i64) {
+        self.nano_time.fetch_add(delta_ns, Ordering::AcqRel);
+    }
+}
+
+impl EpochClock for TestClock {
+    fn time(&self) -> i64 {
+        self.nano_time.load(Ordering::Acquire) / 1_000_000
+    }
+}
+
+impl NanoClock for TestClock {
+    fn nano_time(&self) -> i64 {
+        self.nano_time.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,146 @@
+use std::io;
+use std::str::FromStr;
+
+/// Where the current thread should run: the set of CPUs it is pinned to and, optionally, a
+/// `SCHED_FIFO` real-time priority.
+///
+/// Can be parsed from configuration as `cpus=<list>;fifo=<priority>`, where either part may be
+/// left out and the list is comma separated CPUs or ranges, e.g. `cpus=2,4-5;fifo=50`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadAffinity {
+    cpus: Vec<usize>,
+    fifo_priority: Option<i32>,
+}
+
+impl ThreadAffinity {
+    pub fn new(cpus: Vec<usize>) -> Self {
+        ThreadAffinity { cpus, fifo_priority: None }
+    }
+
+    pub fn with_fifo_priority(mut self, priority: i32) -> Self {
+        self.fifo_priority = Some(priority);
+        self
+    }
+
+    pub fn cpus(&self) -> &[usize] {
+        &self.cpus
+    }
+
+    pub fn fifo_priority(&self) -> Option<i32> {
+        self.fifo_priority
+    }
+
+    /// Apply to the calling thread. Nothing is changed for an empty CPU list or no priority.
+    pub fn apply(&self) -> io::Result<()> {
+        if !self.cpus.is_empty() {
+            set_cpus(&self.cpus)?;
+        }
+        if let Some(priority) = self.fifo_priority {
+            set_fifo_priority(priority)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ThreadAffinity {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> io::Result<Self> {
+        let mut affinity = ThreadAffinity::default();
+        for setting in s.split(';').map(str::trim).filter(|setting| !setting.is_empty()) {
+            match setting.split_once('=') {
+                Some(("cpus", cpus)) => affinity.cpus = parse_cpu_list(cpus)?,
+                Some(("fifo", priority)) => affinity.fifo_priority = Some(parse(priority)?),
+                _ => return Err(invalid_input(format!("invalid thread affinity setting: {}", setting))),
+            }
+        }
+        Ok(affinity)
+    }
+}
+
+/// Parse a CPU list in the format of `/sys/devices/system/cpu/online`, e.g. `0,2-3`.
+pub fn parse_cpu_list(list: &str) -> io::Result<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for range in list.split(',').map(str::trim).filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((first, last)) => {
+                let (first, last): (usize, usize) = (parse(first)?, parse(last)?);
+                if first > last {
+                    return Err(invalid_input(format!("invalid cpu range: {}", range)));
+                }
+                cpus.extend(first..=last);
+            }
+            None => cpus.push(parse(range)?),
+        }
+    }
+    Ok(cpus)
+}
+
+fn parse<T: FromStr>(value: &str) -> io::Result<T> {
+    value.trim().parse().map_err(|_| invalid_input(format!("invalid number: {}", value)))
+}
+
+fn invalid_input(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// The CPUs the calling thread is allowed to run on.
+#[cfg(target_os = "linux")]
+pub fn current_cpus() -> io::Result<Vec<usize>> {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok((0..libc::CPU_SETSIZE as usize).filter(|&cpu| libc::CPU_ISSET(cpu, &set)).collect())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn set_cpus(cpus: &[usize]) -> io::Result<()> {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus {
+            if cpu >= libc::CPU_SETSIZE as usize {
+                return Err(invalid_input(format!("cpu={} is beyond CPU_SETSIZE={}", cpu, libc::CPU_SETSIZE)));
+            }
+            libc::CPU_SET(cpu, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn set_fifo_priority(priority: i32) -> io::Result<()> {
+    unsafe {
+        let min = libc::sched_get_priority_min(libc::SCHED_FIFO);
+        let max = libc::sched_get_priority_max(libc::SCHED_FIFO);
+        if priority < min || priority > max {
+            return Err(invalid_input(format!("SCHED_FIFO priority={} not in range {}-{}", priority, min, max)));
+        }
+        let param = libc::sched_param { sched_priority: priority };
+        let result = libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param);
+        if result != 0 {
+            return Err(io::Error::from_raw_os_error(result));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn current_cpus() -> io::Result<Vec<usize>> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "thread affinity is only supported on Linux"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_cpus(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "thread affinity is only supported on Linux"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn set_fifo_priority(_priority: i32) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "SCHED_FIFO is only supported on Linux"))
+}
@@ -1,19 +1,33 @@
-use std::alloc::alloc;
+use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error};
 use std::alloc::Layout;
+use std::io;
+use std::path::Path;
+use crate::agrona::io_util;
+use crate::agrona::ascii_number_format_error::AsciiNumberFormatError;
+use crate::agrona::buffer_error::BufferError;
 use crate::agrona::concurrent::atomic_buffer::AtomicBuffer;
 use crate::agrona::concurrent::ringbuffer::ring_buffer_descriptor::TRAILER_LENGTH;
-use crate::agrona::direct_buffer::DirectBuffer;
-use std::{intrinsics, ptr};
-use std::sync::atomic::{fence, Ordering};
+use crate::agrona::direct_buffer::{DirectBuffer, STR_HEADER_LEN};
+use std::{intrinsics, mem, ptr, slice, str};
+use std::sync::atomic::{fence, AtomicI32, AtomicI64, Ordering};
 use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
 
-const SHOULD_BOUNDS_CHECK: bool = false;
+// on in debug builds, and in release builds with the `bounds-checks` feature
+const SHOULD_BOUNDS_CHECK: bool = cfg!(any(debug_assertions, feature = "bounds-checks"));
 pub struct UnsafeBuffer {
     // not sure if to use a bytebuffer
     wrap_adjustment: i32,
     byte_array: *mut u8,
     address_offset: i32,
     capacity: i32,
+    allocation: Allocation,
+}
+
+/// Who owns the memory behind an `UnsafeBuffer` and so how it is released on drop.
+enum Allocation {
+    Heap(Layout),
+    Mapped(usize),
+    Wrapped,
 }
 
 #[inline]
@@ -49,59 +63,248 @@ unsafe fn atomic_store<T: Copy>(dst: *mut T, val: T, order: Ordering) {
 }
 
 impl UnsafeBuffer {
+    /// Allocate a buffer for a ring buffer holding `capacity` bytes of messages. `TRAILER_LENGTH`
+    /// is added on top, so the buffer capacity is `capacity + TRAILER_LENGTH`.
     pub fn new(capacity: usize) -> Self {
-        let actual_capacity = capacity + TRAILER_LENGTH as usize;
-        let layout = Layout::array::<u8>(actual_capacity).expect("Invalid layout");
+        Self::allocate_direct(capacity + TRAILER_LENGTH as usize)
+    }
+
+    /// Allocate exactly `actual_capacity` zeroed bytes, like Java's `ByteBuffer.allocateDirect`.
+    /// Use this with `ring_buffer_descriptor::buffer_length` when sizing a buffer explicitly, e.g.
+    /// to match one allocated by Agrona.
+    pub fn allocate_direct(actual_capacity: usize) -> Self {
+        if actual_capacity == 0 {
+            // a zero-sized layout must not be passed to the allocator, and an empty buffer never
+            // dereferences its address, so it needs no memory at all
+            return UnsafeBuffer {
+                wrap_adjustment: 0,
+                byte_array: ptr::NonNull::dangling().as_ptr(),
+                address_offset: 0,
+                capacity: 0,
+                allocation: Allocation::Wrapped,
+            };
+        }
+        let layout = Layout::from_size_align(actual_capacity, io_util::page_size()).expect("Invalid layout");
 
-        // Allocate memory
-        let byte_array = unsafe { alloc(layout) };
+        // Allocate page aligned memory so the trailer counters never straddle a page
+        let byte_array = unsafe { alloc_zeroed(layout) };
+        if byte_array.is_null() {
+            handle_alloc_error(layout);
+        }
 
         UnsafeBuffer {
             wrap_adjustment: 0,
             byte_array,
             address_offset: 0,
             capacity: actual_capacity as i32,
+            allocation: Allocation::Heap(layout),
         }
     }
 
-    fn bounds_check0(&self, index: i32, length: i32) -> Result<(), String> {
-        let resulting_position = index + length;
-        if index < 0 || length < 0 || resulting_position > self.capacity {
-            let tmp = &self.capacity;
-            return Err(format!("index={index} length={length} capacity={tmp}"));
+    /// Wrap memory owned elsewhere, e.g. to get a second view of the same buffer.
+    ///
+    /// # Safety
+    /// `address` must be valid for reads and writes of `length` bytes for the lifetime of the
+    /// returned buffer, and suitably aligned for the atomic accessors.
+    pub unsafe fn wrap(address: *mut u8, length: i32) -> Self {
+        UnsafeBuffer {
+            wrap_adjustment: 0,
+            byte_array: address,
+            address_offset: 0,
+            capacity: length,
+            allocation: Allocation::Wrapped,
+        }
+    }
+
+    /// Create a file of `capacity` + `TRAILER_LENGTH` bytes, e.g. in `/dev/shm`, and map it so the
+    /// buffer can back a ring buffer shared with another process.
+    pub fn map_new_file<P: AsRef<Path>>(path: P, capacity: usize) -> io::Result<Self> {
+        let length = capacity + TRAILER_LENGTH as usize;
+        let byte_array = io_util::map_new_file(path.as_ref(), length)?;
+
+        Ok(UnsafeBuffer {
+            wrap_adjustment: 0,
+            byte_array,
+            address_offset: 0,
+            capacity: length as i32,
+            allocation: Allocation::Mapped(length),
+        })
+    }
+
+    /// Map an existing file created by `map_new_file` (or by Agrona), using the whole file,
+    /// trailer included, as the buffer.
+    pub fn map_existing_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let (byte_array, length) = io_util::map_existing_file(path.as_ref())?;
+        if length > i32::MAX as usize {
+            unsafe { io_util::unmap(byte_array, length) };
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("file too large to map: length={}", length)));
+        }
+
+        Ok(UnsafeBuffer {
+            wrap_adjustment: 0,
+            byte_array,
+            address_offset: 0,
+            capacity: length as i32,
+            allocation: Allocation::Mapped(length),
+        })
+    }
+
+    /// Unlink the file behind a mapped buffer. The mapping stays valid until the buffer is dropped.
+    pub fn unlink<P: AsRef<Path>>(path: P) -> io::Result<()> {
+        io_util::delete(path.as_ref(), false)
+    }
+
+    // string lengths come from the buffer itself, so are checked whether or not bounds checks are on
+    fn get_str_length(&self, index: i32) -> Result<i32, BufferError> {
+        self.bounds_check0(index, STR_HEADER_LEN)?;
+        Ok(Self::get_unaligned(self, index))
+    }
+
+    fn checked_bytes(&self, index: i32, length: i32) -> Result<&[u8], BufferError> {
+        self.bounds_check0(index, length)?;
+        Ok(Self::bytes(self, index, length))
+    }
+
+    fn bytes(&self, index: i32, length: i32) -> &[u8] {
+        Self::check_bounds(self, index, length);
+        unsafe {
+            slice::from_raw_parts(self.byte_array.add((self.address_offset + index) as usize), length as usize)
+        }
+    }
+
+    // for values that need not be aligned, e.g. the length prefix of a string, so are not
+    // accessed atomically
+    fn get_unaligned<T: Copy>(&self, index: i32) -> T {
+        Self::check_bounds(self, index, mem::size_of::<T>() as i32);
+        unsafe {
+            ptr::read_unaligned(self.byte_array.add((self.address_offset + index) as usize) as *const T)
+        }
+    }
+
+    fn put_unaligned<T>(&self, index: i32, value: T) {
+        Self::check_bounds(self, index, mem::size_of::<T>() as i32);
+        unsafe {
+            ptr::write_unaligned(self.byte_array.add((self.address_offset + index) as usize) as *mut T, value);
+        }
+    }
+
+    // accumulates negative values towards i64::MIN, which has no positive counterpart
+    fn parse_digits(&self, index: i32, length: i32, signed: bool) -> Result<i64, AsciiNumberFormatError> {
+        if length <= 0 {
+            return Err(AsciiNumberFormatError::Empty { index, length });
+        }
+
+        let bytes = self.bytes(index, length);
+        // a lone '-' is reported as an invalid digit
+        let negative = signed && length > 1 && bytes[0] == b'-';
+        let mut value: i64 = 0;
+        for (i, &byte) in bytes.iter().enumerate().skip(negative as usize) {
+            if !byte.is_ascii_digit() {
+                return Err(AsciiNumberFormatError::InvalidDigit { index: index + i as i32, value: byte });
+            }
+            let digit = (byte - b'0') as i64;
+            value = value.checked_mul(10)
+                .and_then(|value| if negative { value.checked_sub(digit) } else { value.checked_add(digit) })
+                .ok_or(AsciiNumberFormatError::Overflow { index, length })?;
+        }
+        Ok(value)
+    }
+
+    fn put_unsigned_ascii(&self, index: i32, value: u64) -> i32 {
+        let length = digit_count(value);
+        Self::put_digits_from_end(self, value, index + length);
+        length
+    }
+
+    fn put_digits_from_end(&self, mut value: u64, end_exclusive: i32) -> i32 {
+        let mut index = end_exclusive;
+        loop {
+            index -= 1;
+            self.put_byte(index, b'0' + (value % 10) as u8);
+            value /= 10;
+            if value == 0 {
+                return index;
+            }
+        }
+    }
+
+    #[inline]
+    fn check_bounds(&self, index: i32, length: i32) {
+        if SHOULD_BOUNDS_CHECK {
+            if let Err(e) = self.bounds_check0(index, length) {
+                panic!("{}", e);
+            }
+        }
+    }
+
+    fn bounds_check0(&self, index: i32, length: i32) -> Result<(), BufferError> {
+        // widened so an index near i32::MAX can not wrap round to pass the check
+        let resulting_position = index as i64 + length as i64;
+        if index < 0 || length < 0 || resulting_position > self.capacity as i64 {
+            return Err(BufferError::IndexOutOfBounds { index, length, capacity: self.capacity });
         }
         Ok(())
     }
 
-    fn ensure_capacity(&mut self, index: i32, length: i32) -> Result<(), String> {
+    fn ensure_capacity(&mut self, index: i32, length: i32) -> Result<(), BufferError> {
         if SHOULD_BOUNDS_CHECK {
             self.bounds_check0(index, length)?;
         }
         Ok(())
     }
 
-    fn bounds_check_wrap(&self, offset: i32, length: i32, capacity: i32) -> Result<(), String> {
+    fn bounds_check_wrap(&self, offset: i32, length: i32, capacity: i32) -> Result<(), BufferError> {
         if offset < 0 {
-            return Err(format!("invalid offset={}", offset));
+            return Err(BufferError::InvalidOffset(offset));
         }
 
         if length < 0 {
-            return Err(format!("invalid length={}", length));
+            return Err(BufferError::InvalidLength(length));
         }
 
         if (offset > capacity - length) || (length > capacity - offset) {
-            return Err(format!("offset={} length={} not valid for capacity={}", offset, length, capacity));
+            return Err(BufferError::InvalidWrap { offset, length, capacity });
         }
         Ok(())
     }
 }
 
+// both buffers may be the same, or wrap the same memory, so the ranges can overlap
+fn copy(src_buffer: &UnsafeBuffer, src_index: i32, dst_buffer: &UnsafeBuffer, dst_index: i32, length: i32) {
+    src_buffer.check_bounds(src_index, length);
+    dst_buffer.check_bounds(dst_index, length);
+    unsafe {
+        let src = src_buffer.byte_array.add((src_buffer.address_offset + src_index) as usize);
+        let dst = dst_buffer.byte_array.add((dst_buffer.address_offset + dst_index) as usize);
+        ptr::copy(src, dst, length as usize);
+    }
+}
+
+fn digit_count(value: u64) -> i32 {
+    value.checked_ilog10().map_or(1, |log| log as i32 + 1)
+}
+
+fn natural(value: i64) -> Result<u64, AsciiNumberFormatError> {
+    u64::try_from(value).map_err(|_| AsciiNumberFormatError::NegativeValue(value))
+}
+
+impl Drop for UnsafeBuffer {
+    fn drop(&mut self) {
+        match self.allocation {
+            Allocation::Heap(layout) => unsafe { dealloc(self.byte_array, layout) },
+            Allocation::Mapped(length) => unsafe { io_util::unmap(self.byte_array, length) },
+            Allocation::Wrapped => {}
+        }
+    }
+}
+
 impl AtomicBuffer for UnsafeBuffer {
     fn verify_alignment(&self) {
         // only need check alignemnt if we are dealing with raw bytes
     }
 
     fn get_long_volatile(&self, index: i32) -> i64 {
+        Self::check_bounds(self, index, 8);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const i64;
             atomic_load(ptr, SeqCst)
@@ -110,6 +313,7 @@ impl AtomicBuffer for UnsafeBuffer {
     }
 
     fn put_long_volatile(&mut self, index: i32, value: i64) {
+        Self::check_bounds(self, index, 8);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
             atomic_store(ptr, value, SeqCst);
@@ -117,6 +321,7 @@ impl AtomicBuffer for UnsafeBuffer {
     }
 
     fn put_long_ordered(&mut self, index: i32, value: i64) {
+        Self::check_bounds(self, index, 8);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
             atomic_store(ptr, value, Release);
@@ -124,22 +329,40 @@ impl AtomicBuffer for UnsafeBuffer {
     }
 
     fn add_long_ordered(&self, index: i32, increment: i64) {
-        todo!()
+        Self::check_bounds(self, index, 8);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
+            let value = atomic_load(ptr, Relaxed);
+            atomic_store(ptr, value.wrapping_add(increment), Release);
+        }
     }
 
-    fn compare_and_set_long(&self, index: i32, expected_value: i64, update_value: i64) {
-        todo!()
+    fn compare_and_set_long(&self, index: i32, expected_value: i64, update_value: i64) -> bool {
+        Self::check_bounds(self, index, 8);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
+            AtomicI64::from_ptr(ptr).compare_exchange(expected_value, update_value, SeqCst, SeqCst).is_ok()
+        }
     }
 
     fn get_and_set_long(&self, index: i32, value: i64) -> i64 {
-        todo!()
+        Self::check_bounds(self, index, 8);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
+            AtomicI64::from_ptr(ptr).swap(value, SeqCst)
+        }
     }
 
     fn get_and_add_long(&self, index: i32, delta: i64) -> i64 {
-        todo!()
+        Self::check_bounds(self, index, 8);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
+            AtomicI64::from_ptr(ptr).fetch_add(delta, SeqCst)
+        }
     }
 
     fn get_int_volatile(&self, index: i32) -> i32 {
+        Self::check_bounds(self, index, 4);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const i32;
             // ptr::read_volatile(ptr)
@@ -148,6 +371,7 @@ impl AtomicBuffer for UnsafeBuffer {
     }
 
     fn put_int_volatile(&mut self, index: i32, value: i32) {
+        Self::check_bounds(self, index, 4);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
             // ptr::write_volatile(ptr, value)
@@ -156,6 +380,7 @@ impl AtomicBuffer for UnsafeBuffer {
     }
 
     fn put_int_ordered(&mut self, index: i32, value: i32) {
+        Self::check_bounds(self, index, 4);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
             // ptr::write_unaligned(ptr, value)
@@ -163,44 +388,85 @@ impl AtomicBuffer for UnsafeBuffer {
         }
     }
 
-    fn add_int_ordered(&mut self, index: i32, increment: i32) {
-        todo!()
+    fn add_int_ordered(&self, index: i32, increment: i32) {
+        Self::check_bounds(self, index, 4);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
+            let value = atomic_load(ptr, Relaxed);
+            atomic_store(ptr, value.wrapping_add(increment), Release);
+        }
     }
 
-    fn compare_and_set_int(&mut self, index: i32, expected_value: i32, update_value: i32) -> Result<bool, String> {
-        todo!()
+    fn compare_and_set_int(&self, index: i32, expected_value: i32, update_value: i32) -> bool {
+        Self::check_bounds(self, index, 4);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
+            AtomicI32::from_ptr(ptr).compare_exchange(expected_value, update_value, SeqCst, SeqCst).is_ok()
+        }
     }
 
     fn get_and_set_int(&self, index: i32, value: i32) -> i32 {
-        todo!()
+        Self::check_bounds(self, index, 4);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
+            AtomicI32::from_ptr(ptr).swap(value, SeqCst)
+        }
     }
 
     fn get_and_add_int(&self, index: i32, delta: i32) -> i32 {
-        todo!()
+        Self::check_bounds(self, index, 4);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
+            AtomicI32::from_ptr(ptr).fetch_add(delta, SeqCst)
+        }
     }
 
     fn get_short_volatile(&self, index: i32) -> i16 {
-        todo!()
+        Self::check_bounds(self, index, 2);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const i16;
+            atomic_load(ptr, SeqCst)
+        }
     }
 
     fn put_short_volatile(&self, index: i32, value: i16) {
-        todo!()
+        Self::check_bounds(self, index, 2);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i16;
+            atomic_store(ptr, value, SeqCst);
+        }
     }
 
-    fn get_char_volatile(&self, index: i32) -> char {
-        todo!()
+    fn get_char_volatile(&self, index: i32) -> u16 {
+        Self::check_bounds(self, index, 2);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const u16;
+            atomic_load(ptr, SeqCst)
+        }
     }
 
-    fn put_char_volatile(&self, index: i32, value: char) {
-        todo!()
+    fn put_char_volatile(&self, index: i32, value: u16) {
+        Self::check_bounds(self, index, 2);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut u16;
+            atomic_store(ptr, value, SeqCst);
+        }
     }
 
     fn get_byte_volatile(&self, index: i32) -> u8 {
-        todo!()
+        Self::check_bounds(self, index, 1);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const u8;
+            atomic_load(ptr, SeqCst)
+        }
     }
 
     fn put_byte_ordered(&self, index: i32, value: u8) {
-        todo!()
+        Self::check_bounds(self, index, 1);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize);
+            atomic_store(ptr, value, Release);
+        }
     }
 }
 
@@ -217,14 +483,15 @@ impl DirectBuffer for UnsafeBuffer {
         self.capacity
     }
 
-    fn check_limit(&self, limit: i32) -> Result<(), String> {
+    fn check_limit(&self, limit: i32) -> Result<(), BufferError> {
         if limit > self.capacity {
-            return Err(format!("limit={} is beyond capacity={}", limit, self.capacity));
+            return Err(BufferError::LimitBeyondCapacity { limit, capacity: self.capacity });
         }
         Ok(())
     }
 
     fn get_long(&self, index: i32) -> i64 {
+        Self::check_bounds(self, index, 8);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const i64;
             // ptr::read_unaligned(ptr)
@@ -233,6 +500,7 @@ impl DirectBuffer for UnsafeBuffer {
     }
 
     fn get_int(&self, index: i32) -> i32 {
+        Self::check_bounds(self, index, 4);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *const i32;
             // ptr::read_unaligned(ptr)
@@ -240,77 +508,156 @@ impl DirectBuffer for UnsafeBuffer {
         }
     }
 
-    fn parse_natural_int_ascii(&self, index: i32, length: i32) -> i32 {
-        todo!()
+    fn parse_natural_int_ascii(&self, index: i32, length: i32) -> Result<i32, AsciiNumberFormatError> {
+        let value = Self::parse_digits(self, index, length, false)?;
+        i32::try_from(value).map_err(|_| AsciiNumberFormatError::Overflow { index, length })
     }
 
-    fn parse_natural_long_ascii(&self, index: i32, length: i32) -> i64 {
-        todo!()
+    fn parse_natural_long_ascii(&self, index: i32, length: i32) -> Result<i64, AsciiNumberFormatError> {
+        Self::parse_digits(self, index, length, false)
     }
 
-    fn parse_int_ascii(&self, index: i32, length: i32) -> i32 {
-        todo!()
+    fn parse_int_ascii(&self, index: i32, length: i32) -> Result<i32, AsciiNumberFormatError> {
+        let value = Self::parse_long_ascii(self, index, length)?;
+        i32::try_from(value).map_err(|_| AsciiNumberFormatError::Overflow { index, length })
     }
 
-    fn parse_long_ascii(&self, index: i32, length: i32) -> i64 {
-        todo!()
+    fn parse_long_ascii(&self, index: i32, length: i32) -> Result<i64, AsciiNumberFormatError> {
+        Self::parse_digits(self, index, length, true)
     }
 
     fn get_double(&self, index: i32) -> f64 {
-        todo!()
+        Self::get_unaligned(self, index)
     }
 
     fn get_float(&self, index: i32) -> f32 {
-        todo!()
+        Self::get_unaligned(self, index)
     }
 
     fn get_short(&self, index: i32) -> i16 {
-        todo!()
+        Self::get_unaligned(self, index)
     }
 
-    fn get_char(&self, index: i32) -> char {
-        todo!()
+    fn get_char(&self, index: i32) -> u16 {
+        Self::get_unaligned(self, index)
     }
 
-    // fn get_string_ascii(&self, index: i32) -> String {
-    //     todo!()
-    // }
+    fn get_short_be(&self, index: i32) -> i16 {
+        i16::from_be(Self::get_unaligned(self, index))
+    }
 
-    // fn get_string_without_length_ascii(&self, index: i32, length: i32) -> String {
-    //     todo!()
-    // }
+    fn get_short_le(&self, index: i32) -> i16 {
+        i16::from_le(Self::get_unaligned(self, index))
+    }
 
-    // fn get_string_utf8(&self, index: i32) -> String {
-    //     todo!()
-    // }
+    fn get_int_be(&self, index: i32) -> i32 {
+        i32::from_be(Self::get_unaligned(self, index))
+    }
 
-    // fn get_string_utf8_0(&self, index: i32, length: i32) -> String {
-    //     todo!()
-    // }
+    fn get_int_le(&self, index: i32) -> i32 {
+        i32::from_le(Self::get_unaligned(self, index))
+    }
 
-    // fn get_string_without_length_utf8(&self, index: i32, length: i32) -> String {
-    //     todo!()
-    // }
+    fn get_long_be(&self, index: i32) -> i64 {
+        i64::from_be(Self::get_unaligned(self, index))
+    }
 
-    // fn get_byte(&self, index: i32) -> u8 {
-    //     todo!()
-    // }
-    //
-    // fn get_bytes(&self, index: i32, dst: &[u8]) {
-    //     todo!()
-    // }
-    //
-    // fn get_bytes0(&self, index: i32, dst: &[u8], offset: i32, length: i32) {
-    //     todo!()
-    // }
-    //
-    // fn get_bytes1(&self, index: i32, dst_buffer: &UnsafeBuffer, offset: i32, length: i32) {
-    //     todo!()
-    // }
-    //
-    // fn bounds_check(&self, index: i32, length: i32) {
-    //     todo!()
-    // }
+    fn get_long_le(&self, index: i32) -> i64 {
+        i64::from_le(Self::get_unaligned(self, index))
+    }
+
+    fn get_float_be(&self, index: i32) -> f32 {
+        f32::from_bits(u32::from_be(Self::get_unaligned(self, index)))
+    }
+
+    fn get_float_le(&self, index: i32) -> f32 {
+        f32::from_bits(u32::from_le(Self::get_unaligned(self, index)))
+    }
+
+    fn get_double_be(&self, index: i32) -> f64 {
+        f64::from_bits(u64::from_be(Self::get_unaligned(self, index)))
+    }
+
+    fn get_double_le(&self, index: i32) -> f64 {
+        f64::from_bits(u64::from_le(Self::get_unaligned(self, index)))
+    }
+
+    fn get_string_ascii(&self, index: i32) -> Result<String, BufferError> {
+        let mut dst = String::new();
+        Self::get_string_ascii_into(self, index, &mut dst)?;
+        Ok(dst)
+    }
+
+    fn get_string_ascii_into(&self, index: i32, dst: &mut String) -> Result<i32, BufferError> {
+        let length = Self::get_str_length(self, index)?;
+        Self::get_string_without_length_ascii_into(self, index + STR_HEADER_LEN, length, dst)
+    }
+
+    fn get_string_without_length_ascii(&self, index: i32, length: i32) -> Result<String, BufferError> {
+        let mut dst = String::new();
+        Self::get_string_without_length_ascii_into(self, index, length, &mut dst)?;
+        Ok(dst)
+    }
+
+    fn get_string_without_length_ascii_into(&self, index: i32, length: i32, dst: &mut String) -> Result<i32, BufferError> {
+        let bytes = Self::checked_bytes(self, index, length)?;
+        dst.extend(bytes.iter().map(|&byte| if byte.is_ascii() { byte as char } else { '?' }));
+        Ok(length)
+    }
+
+    fn get_string_utf8(&self, index: i32) -> Result<String, BufferError> {
+        Self::get_str_utf8(self, index).map(String::from)
+    }
+
+    fn get_string_utf8_0(&self, index: i32, length: i32) -> Result<String, BufferError> {
+        Self::get_str_without_length_utf8(self, index + STR_HEADER_LEN, length).map(String::from)
+    }
+
+    fn get_string_utf8_into(&self, index: i32, dst: &mut String) -> Result<i32, BufferError> {
+        let value = Self::get_str_utf8(self, index)?;
+        dst.push_str(value);
+        Ok(value.len() as i32)
+    }
+
+    fn get_string_without_length_utf8(&self, index: i32, length: i32) -> Result<String, BufferError> {
+        Self::get_str_without_length_utf8(self, index, length).map(String::from)
+    }
+
+    fn get_str_utf8(&self, index: i32) -> Result<&str, BufferError> {
+        let length = Self::get_str_length(self, index)?;
+        Self::get_str_without_length_utf8(self, index + STR_HEADER_LEN, length)
+    }
+
+    fn get_str_without_length_utf8(&self, index: i32, length: i32) -> Result<&str, BufferError> {
+        str::from_utf8(Self::checked_bytes(self, index, length)?).map_err(|error| BufferError::InvalidUtf8 { index, error })
+    }
+
+    fn get_byte(&self, index: i32) -> u8 {
+        Self::check_bounds(self, index, 1);
+        unsafe {
+            *self.byte_array.add((self.address_offset + index) as usize)
+        }
+    }
+
+    fn get_bytes(&self, index: i32, dst: &mut [u8]) {
+        Self::check_bounds(self, index, dst.len() as i32);
+        unsafe {
+            let src = self.byte_array.add((self.address_offset + index) as usize);
+            ptr::copy_nonoverlapping(src, dst.as_mut_ptr(), dst.len());
+        }
+    }
+
+    fn get_bytes0(&self, index: i32, dst: &mut [u8], offset: i32, length: i32) {
+        Self::get_bytes(self, index, &mut dst[offset as usize..(offset + length) as usize]);
+    }
+
+    fn get_bytes1(&self, index: i32, dst_buffer: &UnsafeBuffer, offset: i32, length: i32) {
+        copy(self, index, dst_buffer, offset, length);
+    }
+
+    fn bounds_check(&self, index: i32, length: i32) -> Result<(), BufferError> {
+        self.bounds_check0(index, length)
+    }
 
     fn wrap_adjustment(&self) -> i32 {
         self.wrap_adjustment
@@ -328,10 +675,15 @@ impl DirectBuffer for UnsafeBuffer {
     }
 
     fn set_memory(&self, index: i32, length: i32, value: u8) {
-        todo!()
+        Self::check_bounds(self, index, length);
+        unsafe {
+            let ptr = self.byte_array.add((self.address_offset + index) as usize);
+            ptr::write_bytes(ptr, value, length as usize);
+        }
     }
 
     fn put_long(&mut self, index: i32, value: i64) {
+        Self::check_bounds(self, index, 8);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i64;
             // ptr::write_unaligned(ptr, value)
@@ -340,6 +692,7 @@ impl DirectBuffer for UnsafeBuffer {
     }
 
     fn put_int(&mut self, index: i32, value: i32) {
+        Self::check_bounds(self, index, 4);
         unsafe {
             let ptr = self.byte_array.add((self.address_offset + index) as usize) as *mut i32;
             // ptr::write_unaligned(ptr, value)
@@ -348,78 +701,160 @@ impl DirectBuffer for UnsafeBuffer {
     }
 
     fn put_int_ascii(&self, index: i32, value: i32) -> i32 {
-        todo!()
+        Self::put_long_ascii(self, index, value as i64)
     }
 
-    fn put_natural_int_ascii(&self, index: i32, value: i32) -> i32 {
-        todo!()
+    fn put_natural_int_ascii(&self, index: i32, value: i32) -> Result<i32, AsciiNumberFormatError> {
+        Self::put_natural_long_ascii(self, index, value as i64)
     }
 
-    fn put_natural_padding_int_ascii(&self, index: i32, length: i32, value: i64) {
-        todo!()
+    fn put_natural_padding_int_ascii(&self, index: i32, length: i32, value: i32) -> Result<(), AsciiNumberFormatError> {
+        let magnitude = natural(value as i64)?;
+        let digits = digit_count(magnitude);
+        if digits > length {
+            return Err(AsciiNumberFormatError::ValueTooLong { value: value as i64, length });
+        }
+
+        self.set_memory(index, length - digits, b'0');
+        Self::put_digits_from_end(self, magnitude, index + length);
+        Ok(())
     }
 
-    fn put_natural_int_ascii_from_end(&self, value: i32, end_exclusive: i32) -> i32 {
-        todo!()
+    fn put_natural_int_ascii_from_end(&self, value: i32, end_exclusive: i32) -> Result<i32, AsciiNumberFormatError> {
+        Ok(Self::put_digits_from_end(self, natural(value as i64)?, end_exclusive))
     }
 
-    fn put_natural_long_ascii(&self, index: i32, value: i64) -> i32 {
-        todo!()
+    fn put_natural_long_ascii(&self, index: i32, value: i64) -> Result<i32, AsciiNumberFormatError> {
+        let magnitude = natural(value)?;
+        Ok(Self::put_unsigned_ascii(self, index, magnitude))
     }
 
     fn put_long_ascii(&self, index: i32, value: i64) -> i32 {
-        todo!()
+        if value >= 0 {
+            return Self::put_unsigned_ascii(self, index, value as u64);
+        }
+
+        self.put_byte(index, b'-');
+        Self::put_unsigned_ascii(self, index + 1, value.unsigned_abs()) + 1
     }
 
     fn put_double(&self, index: i32, value: f64) {
-        todo!()
+        Self::put_unaligned(self, index, value)
     }
 
     fn put_float(&self, index: i32, value: f32) {
-        todo!()
+        Self::put_unaligned(self, index, value)
     }
 
     fn put_short(&self, index: i32, value: i16) {
-        todo!()
+        Self::put_unaligned(self, index, value)
+    }
+
+    fn put_char(&self, index: i32, value: u16) {
+        Self::put_unaligned(self, index, value)
+    }
+
+    fn put_short_be(&self, index: i32, value: i16) {
+        Self::put_unaligned(self, index, value.to_be())
     }
 
-    fn put_char(&self, index: i32, value: char) {
-        todo!()
+    fn put_short_le(&self, index: i32, value: i16) {
+        Self::put_unaligned(self, index, value.to_le())
+    }
+
+    fn put_int_be(&self, index: i32, value: i32) {
+        Self::put_unaligned(self, index, value.to_be())
+    }
+
+    fn put_int_le(&self, index: i32, value: i32) {
+        Self::put_unaligned(self, index, value.to_le())
+    }
+
+    fn put_long_be(&self, index: i32, value: i64) {
+        Self::put_unaligned(self, index, value.to_be())
+    }
+
+    fn put_long_le(&self, index: i32, value: i64) {
+        Self::put_unaligned(self, index, value.to_le())
+    }
+
+    fn put_float_be(&self, index: i32, value: f32) {
+        Self::put_unaligned(self, index, value.to_bits().to_be())
+    }
+
+    fn put_float_le(&self, index: i32, value: f32) {
+        Self::put_unaligned(self, index, value.to_bits().to_le())
+    }
+
+    fn put_double_be(&self, index: i32, value: f64) {
+        Self::put_unaligned(self, index, value.to_bits().to_be())
+    }
+
+    fn put_double_le(&self, index: i32, value: f64) {
+        Self::put_unaligned(self, index, value.to_bits().to_le())
     }
 
     fn put_byte(&self, index: i32, value: u8) {
-        todo!()
+        Self::check_bounds(self, index, 1);
+        unsafe {
+            *self.byte_array.add((self.address_offset + index) as usize) = value;
+        }
     }
 
-    fn put_bytes(&self, index: i32, bytes: &UnsafeBuffer) {
-        todo!()
+    fn put_bytes(&self, index: i32, bytes: &[u8]) {
+        Self::check_bounds(self, index, bytes.len() as i32);
+        unsafe {
+            let dst = self.byte_array.add((self.address_offset + index) as usize);
+            ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len());
+        }
     }
 
     fn put_bytes2(&self, index: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32) {
-        todo!()
+        copy(src_buffer, offset, self, index, length);
     }
 
     fn put_string_ascii(&self, index: i32, value: &str) -> i32 {
-        todo!()
+        let length = Self::put_string_without_length_ascii(self, index + STR_HEADER_LEN, value);
+        Self::put_unaligned(self, index, length);
+        STR_HEADER_LEN + length
     }
 
     fn put_string_without_length_ascii(&self, index: i32, value: &str) -> i32 {
-        todo!()
+        if value.is_ascii() {
+            self.put_bytes(index, value.as_bytes());
+            return value.len() as i32;
+        }
+        Self::put_string_without_length_ascii0(self, index, value, 0, i32::MAX)
     }
 
     fn put_string_without_length_ascii0(&self, index: i32, value: &str, value_offset: i32, length: i32) -> i32 {
-        todo!()
+        let mut written = 0;
+        for c in value.chars().skip(value_offset as usize).take(length as usize) {
+            self.put_byte(index + written, if c.is_ascii() { c as u8 } else { b'?' });
+            written += 1;
+        }
+        written
     }
 
     fn put_string_utf8(&self, index: i32, value: &str) -> i32 {
-        todo!()
+        match Self::put_string_utf8_1(self, index, value, i32::MAX - STR_HEADER_LEN) {
+            Ok(length) => length,
+            Err(e) => panic!("{}", e),
+        }
     }
 
-    fn put_string_utf8_1(&self, index: i32, value: &str, max_encoded_length: i32) -> i32 {
-        todo!()
+    fn put_string_utf8_1(&self, index: i32, value: &str, max_encoded_length: i32) -> Result<i32, BufferError> {
+        if value.len() > max_encoded_length.max(0) as usize {
+            return Err(BufferError::EncodedLengthTooLong { length: value.len(), max_length: max_encoded_length });
+        }
+
+        let length = Self::put_string_without_length_utf8(self, index + STR_HEADER_LEN, value);
+        Self::put_unaligned(self, index, length);
+        Ok(STR_HEADER_LEN + length)
     }
 
     fn put_string_without_length_utf8(&self, index: i32, value: &str) -> i32 {
-        todo!()
+        self.put_bytes(index, value.as_bytes());
+        value.len() as i32
     }
 }
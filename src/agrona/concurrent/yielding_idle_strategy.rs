@@ -0,0 +1,20 @@
+use std::thread;
+use crate::agrona::concurrent::idle_strategy::IdleStrategy;
+
+pub const ALIAS: &str = "yield";
+
+/// Yields the thread to the OS scheduler when idle.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct YieldingIdleStrategy;
+
+impl IdleStrategy for YieldingIdleStrategy {
+    fn idle0(&mut self) {
+        thread::yield_now();
+    }
+
+    fn reset(&mut self) {}
+
+    fn alias(&self) -> &'static str {
+        ALIAS
+    }
+}
@@ -0,0 +1,280 @@
+use crate::agrona::timer_wheel_error::TimerWheelError;
+use crate::bit_util::{is_power_of_two_i32, is_power_of_two_i320};
+
+/// Deadline of a free slot in the wheel.
+pub const NULL_DEADLINE: i64 = i64::MAX;
+
+pub const INITIAL_TICK_ALLOCATION: i32 = 16;
+
+const MAX_CAPACITY: usize = 1 << 30;
+
+/// Timer wheel for timers scheduled to expire on a deadline, ported from Agrona's
+/// `DeadlineTimerWheel`.
+///
+/// Times are in whatever unit the caller's clock gives, e.g. nanoseconds from a `NanoClock`, with
+/// the tick resolution in the same unit. Each tick of the wheel has an array of timer slots which
+/// is only grown, doubling every tick's allocation, when a tick runs out of slots, so scheduling
+/// and cancelling do not allocate once the wheel has warmed up.
+///
+/// The wheel is not thread safe. It is meant to be driven from the duty cycle of an agent, calling
+/// `poll` with the current time and scheduling and cancelling timers on the same thread.
+#[derive(Debug)]
+pub struct DeadlineTimerWheel {
+    tick_resolution: i64,
+    start_time: i64,
+    tick_mask: i32,
+    resolution_bits_to_shift: u32,
+    tick_allocation: i32,
+    allocation_bits_to_shift: u32,
+    poll_index: i32,
+    current_tick: i64,
+    timer_count: i64,
+    wheel: Vec<i64>,
+}
+
+impl DeadlineTimerWheel {
+    /// Construct a timer wheel with `INITIAL_TICK_ALLOCATION` slots per tick.
+    ///
+    /// @param start_time      for the wheel, from which tick 0 starts.
+    /// @param tick_resolution for the wheel, i.e. how many time units per tick.
+    /// @param ticks_per_wheel or spokes, for the wheel.
+    pub fn new(start_time: i64, tick_resolution: i64, ticks_per_wheel: i32) -> Self {
+        match Self::try_new(start_time, tick_resolution, ticks_per_wheel, INITIAL_TICK_ALLOCATION) {
+            Ok(timer_wheel) => timer_wheel,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Construct a timer wheel, checking that the tick resolution, ticks per wheel and initial
+    /// tick allocation are all powers of 2.
+    pub fn try_new(start_time: i64, tick_resolution: i64, ticks_per_wheel: i32, initial_tick_allocation: i32) -> Result<Self, TimerWheelError> {
+        if !is_power_of_two_i32(ticks_per_wheel) {
+            return Err(TimerWheelError::InvalidTicksPerWheel(ticks_per_wheel));
+        }
+        if !is_power_of_two_i320(tick_resolution) {
+            return Err(TimerWheelError::InvalidTickResolution(tick_resolution));
+        }
+        if !is_power_of_two_i32(initial_tick_allocation) {
+            return Err(TimerWheelError::InvalidTickAllocation(initial_tick_allocation));
+        }
+
+        Ok(DeadlineTimerWheel {
+            tick_resolution,
+            start_time,
+            tick_mask: ticks_per_wheel - 1,
+            resolution_bits_to_shift: tick_resolution.trailing_zeros(),
+            tick_allocation: initial_tick_allocation,
+            allocation_bits_to_shift: initial_tick_allocation.trailing_zeros(),
+            poll_index: 0,
+            current_tick: 0,
+            timer_count: 0,
+            wheel: vec![NULL_DEADLINE; ticks_per_wheel as usize * initial_tick_allocation as usize],
+        })
+    }
+
+    pub fn tick_resolution(&self) -> i64 {
+        self.tick_resolution
+    }
+
+    pub fn ticks_per_wheel(&self) -> i32 {
+        self.tick_mask + 1
+    }
+
+    /// Number of slots currently allocated for each tick.
+    pub fn tick_allocation(&self) -> i32 {
+        self.tick_allocation
+    }
+
+    pub fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    /// Number of timers scheduled and not yet expired or cancelled.
+    pub fn timer_count(&self) -> i64 {
+        self.timer_count
+    }
+
+    /// Reset the start time of the wheel, which is only allowed with no active timers.
+    pub fn reset_start_time(&mut self, start_time: i64) -> Result<(), TimerWheelError> {
+        if self.timer_count > 0 {
+            return Err(TimerWheelError::TimersActive(self.timer_count));
+        }
+
+        self.start_time = start_time;
+        self.current_tick = 0;
+        self.poll_index = 0;
+        Ok(())
+    }
+
+    /// Time at which the current tick ends and polling moves on to the next tick.
+    pub fn current_tick_time(&self) -> i64 {
+        ((self.current_tick + 1) << self.resolution_bits_to_shift) + self.start_time
+    }
+
+    /// Set the current tick of the wheel to examine on the next `poll`. Timers in skipped ticks
+    /// are not lost, they expire when polling wraps around to their tick.
+    pub fn set_current_tick_time(&mut self, now: i64) {
+        self.current_tick = ((now - self.start_time) >> self.resolution_bits_to_shift).max(self.current_tick);
+    }
+
+    /// Clear out all active timers in the wheel.
+    pub fn clear(&mut self) {
+        self.wheel.fill(NULL_DEADLINE);
+        self.timer_count = 0;
+    }
+
+    /// Schedule a timer for the given absolute deadline, returning the timer id used to cancel it.
+    /// A deadline in the past expires on the next tick polled.
+    pub fn schedule_timer(&mut self, deadline: i64) -> i64 {
+        let deadline_tick = ((deadline - self.start_time) >> self.resolution_bits_to_shift).max(self.current_tick);
+        let spoke_index = deadline_tick as i32 & self.tick_mask;
+        let tick_start_index = spoke_index << self.allocation_bits_to_shift;
+
+        for i in 0..self.tick_allocation {
+            let index = (tick_start_index + i) as usize;
+            if self.wheel[index] == NULL_DEADLINE {
+                self.wheel[index] = deadline;
+                self.timer_count += 1;
+                return timer_id_for_slot(spoke_index, i);
+            }
+        }
+
+        Self::increase_capacity(self, deadline, spoke_index)
+    }
+
+    /// Cancel a previously scheduled timer, returning false if it has already expired or been
+    /// cancelled.
+    pub fn cancel_timer(&mut self, timer_id: i64) -> bool {
+        let spoke_index = tick_for_timer_id(timer_id);
+        let tick_index = index_in_tick_array(timer_id);
+
+        if spoke_index >= 0 && spoke_index <= self.tick_mask && tick_index >= 0 && tick_index < self.tick_allocation {
+            let wheel_index = ((spoke_index << self.allocation_bits_to_shift) + tick_index) as usize;
+            if self.wheel[wheel_index] != NULL_DEADLINE {
+                self.wheel[wheel_index] = NULL_DEADLINE;
+                self.timer_count -= 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Poll for timers expired by `now`, handing each to `handler` with the time and timer id. The
+    /// handler returns false to keep the timer scheduled and stop this poll, e.g. when it can not
+    /// act on the expiry yet. At most one tick is moved on per poll, so call it every duty cycle.
+    ///
+    /// @param expiry_limit maximum number of timers to expire in one poll.
+    /// @return the number of timers expired.
+    pub fn poll<F>(&mut self, now: i64, mut handler: F, expiry_limit: i32) -> i32 where F: FnMut(i64, i64) -> bool {
+        let mut timers_expired = 0;
+
+        if self.timer_count > 0 {
+            let spoke_index = self.current_tick as i32 & self.tick_mask;
+            let length = self.tick_allocation;
+            let mut i = 0;
+            while i < length && expiry_limit > timers_expired {
+                let wheel_index = ((spoke_index << self.allocation_bits_to_shift) + self.poll_index) as usize;
+                let deadline = self.wheel[wheel_index];
+
+                if now >= deadline {
+                    self.wheel[wheel_index] = NULL_DEADLINE;
+                    self.timer_count -= 1;
+                    timers_expired += 1;
+
+                    if !handler(now, timer_id_for_slot(spoke_index, self.poll_index)) {
+                        self.wheel[wheel_index] = deadline;
+                        self.timer_count += 1;
+                        return timers_expired - 1;
+                    }
+                }
+
+                self.poll_index = if self.poll_index + 1 >= length { 0 } else { self.poll_index + 1 };
+                i += 1;
+            }
+
+            if expiry_limit > timers_expired && self.current_tick_time() <= now {
+                self.current_tick += 1;
+                self.poll_index = 0;
+            } else if self.poll_index >= self.tick_allocation {
+                self.poll_index = 0;
+            }
+        } else if self.current_tick_time() <= now {
+            self.current_tick += 1;
+            self.poll_index = 0;
+        }
+
+        timers_expired
+    }
+
+    /// Deadline of a scheduled timer, or `NULL_DEADLINE` if it is not active.
+    pub fn deadline(&self, timer_id: i64) -> i64 {
+        let spoke_index = tick_for_timer_id(timer_id);
+        let tick_index = index_in_tick_array(timer_id);
+
+        if spoke_index >= 0 && spoke_index <= self.tick_mask && tick_index >= 0 && tick_index < self.tick_allocation {
+            return self.wheel[((spoke_index << self.allocation_bits_to_shift) + tick_index) as usize];
+        }
+
+        NULL_DEADLINE
+    }
+
+    /// Iterate over the active timers with their deadline and timer id.
+    pub fn for_each<F>(&self, mut func: F) where F: FnMut(i64, i64) {
+        let mut timers_remaining = self.timer_count;
+
+        for j in 0..=self.tick_mask {
+            if timers_remaining <= 0 {
+                break;
+            }
+            let tick_start_index = j << self.allocation_bits_to_shift;
+            for i in 0..self.tick_allocation {
+                let deadline = self.wheel[(tick_start_index + i) as usize];
+                if deadline != NULL_DEADLINE {
+                    func(deadline, timer_id_for_slot(j, i));
+                    timers_remaining -= 1;
+                }
+            }
+        }
+    }
+
+    fn increase_capacity(&mut self, deadline: i64, spoke_index: i32) -> i64 {
+        let new_tick_allocation = self.tick_allocation << 1;
+        let new_allocation_bits_to_shift = new_tick_allocation.trailing_zeros();
+        let new_capacity = self.wheel.len() * 2;
+        if new_capacity > MAX_CAPACITY {
+            panic!("max capacity reached at tickAllocation={}", self.tick_allocation);
+        }
+
+        let mut new_wheel = vec![NULL_DEADLINE; new_capacity];
+        let tick_allocation = self.tick_allocation as usize;
+        for j in 0..=self.tick_mask {
+            let old_tick_start_index = (j << self.allocation_bits_to_shift) as usize;
+            let new_tick_start_index = (j << new_allocation_bits_to_shift) as usize;
+            new_wheel[new_tick_start_index..new_tick_start_index + tick_allocation]
+                .copy_from_slice(&self.wheel[old_tick_start_index..old_tick_start_index + tick_allocation]);
+        }
+
+        new_wheel[((spoke_index << new_allocation_bits_to_shift) + self.tick_allocation) as usize] = deadline;
+        self.timer_count += 1;
+        let timer_id = timer_id_for_slot(spoke_index, self.tick_allocation);
+
+        self.tick_allocation = new_tick_allocation;
+        self.allocation_bits_to_shift = new_allocation_bits_to_shift;
+        self.wheel = new_wheel;
+
+        timer_id
+    }
+}
+
+fn timer_id_for_slot(tick_on_wheel: i32, tick_array_index: i32) -> i64 {
+    ((tick_on_wheel as i64) << 32) | tick_array_index as i64
+}
+
+fn tick_for_timer_id(timer_id: i64) -> i32 {
+    (timer_id >> 32) as i32
+}
+
+fn index_in_tick_array(timer_id: i64) -> i32 {
+    timer_id as i32
+}
@@ -1,57 +1,118 @@
+use crate::agrona::ascii_number_format_error::AsciiNumberFormatError;
+use crate::agrona::buffer_error::BufferError;
 use crate::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
 use crate::bit_util::SIZE_OF_INT;
 
-const STR_HEADER_LEN: i32 = SIZE_OF_INT;
+/// Length of the `i32` length prefix written before a string by the `put_string_*` methods.
+pub const STR_HEADER_LEN: i32 = SIZE_OF_INT;
 
 pub trait DirectBuffer {
     fn address_offset(&self) -> i32;
     fn byte_array(&self) -> *mut u8;
     fn capacity(&self) -> i32;
-    fn check_limit(&self, limit: i32) -> Result<(), String>;
+    fn check_limit(&self, limit: i32) -> Result<(), BufferError>;
     fn get_long(&self, index: i32) -> i64;
     fn get_int(&self, index: i32) -> i32;
-    fn parse_natural_int_ascii(&self, index: i32, length: i32) -> i32;
-    fn parse_natural_long_ascii(&self, index: i32, length: i32) -> i64;
-    fn parse_int_ascii(&self, index: i32, length: i32) -> i32;
-    fn parse_long_ascii(&self, index: i32, length: i32) -> i64;
+    /// Parse the ASCII digits in `index..index + length` as a natural number.
+    fn parse_natural_int_ascii(&self, index: i32, length: i32) -> Result<i32, AsciiNumberFormatError>;
+    fn parse_natural_long_ascii(&self, index: i32, length: i32) -> Result<i64, AsciiNumberFormatError>;
+    /// Parse the ASCII digits in `index..index + length`, with an optional leading '-', as a number.
+    fn parse_int_ascii(&self, index: i32, length: i32) -> Result<i32, AsciiNumberFormatError>;
+    fn parse_long_ascii(&self, index: i32, length: i32) -> Result<i64, AsciiNumberFormatError>;
     fn get_double(&self, index: i32) -> f64;
     fn get_float(&self, index: i32) -> f32;
     fn get_short(&self, index: i32) -> i16;
-    fn get_char(&self, index: i32) -> char;
-    // fn get_byte(&self, index: i32) -> u8;
-    // fn get_bytes(&self, index: i32, dst: &[u8]);
-    // fn get_bytes0(&self, index: i32, dst: &[u8], offset: i32, length: i32);
-    // fn get_bytes1(&self, index: i32, dst_buffer: &UnsafeBuffer, offset: i32, length: i32);
-    // fn get_string_ascii(&self, index: i32) -> str;
-    // fn get_string_without_length_ascii(&self, index:i32, length: i32) -> str;
-    // fn get_string_utf8(&self, index:i32) -> str;
-    // fn get_string_utf8_0(&self, index:i32, length: i32) -> String;
-    // fn get_string_without_length_utf8(&self, index:i32, length: i32) -> str;
-    // fn bounds_check(&self, index: i32, length: i32);
+    /// A UTF-16 code unit, see `AtomicBuffer::get_char_volatile`.
+    fn get_char(&self, index: i32) -> u16;
+    /// Get a big-endian, i.e. network order, value whatever the native byte order. These and the
+    /// other `_be` and `_le` accessors need not be aligned.
+    fn get_short_be(&self, index: i32) -> i16;
+    fn get_short_le(&self, index: i32) -> i16;
+    fn get_int_be(&self, index: i32) -> i32;
+    fn get_int_le(&self, index: i32) -> i32;
+    fn get_long_be(&self, index: i32) -> i64;
+    fn get_long_le(&self, index: i32) -> i64;
+    fn get_float_be(&self, index: i32) -> f32;
+    fn get_float_le(&self, index: i32) -> f32;
+    fn get_double_be(&self, index: i32) -> f64;
+    fn get_double_le(&self, index: i32) -> f64;
+    fn get_byte(&self, index: i32) -> u8;
+    /// Copy `dst.len()` bytes from `index` into `dst`.
+    fn get_bytes(&self, index: i32, dst: &mut [u8]);
+    /// Copy `length` bytes from `index` into `dst` starting at `offset`. Panics if the range does
+    /// not fit in `dst`.
+    fn get_bytes0(&self, index: i32, dst: &mut [u8], offset: i32, length: i32);
+    /// Copy `length` bytes from `index` into `dst_buffer` starting at `offset`.
+    fn get_bytes1(&self, index: i32, dst_buffer: &UnsafeBuffer, offset: i32, length: i32);
+    /// Get a length prefixed ASCII string. Bytes outside ASCII are read as '?'. The string getters
+    /// always check the length, as it is read from the buffer, and fail if it is negative or runs
+    /// past the capacity.
+    fn get_string_ascii(&self, index: i32) -> Result<String, BufferError>;
+    /// Append a length prefixed ASCII string to `dst`, returning the number of bytes read.
+    fn get_string_ascii_into(&self, index: i32, dst: &mut String) -> Result<i32, BufferError>;
+    fn get_string_without_length_ascii(&self, index: i32, length: i32) -> Result<String, BufferError>;
+    fn get_string_without_length_ascii_into(&self, index: i32, length: i32, dst: &mut String) -> Result<i32, BufferError>;
+    /// Get a length prefixed UTF-8 string.
+    fn get_string_utf8(&self, index: i32) -> Result<String, BufferError>;
+    /// Get `length` bytes of UTF-8 after the length prefix, ignoring the prefix itself.
+    fn get_string_utf8_0(&self, index: i32, length: i32) -> Result<String, BufferError>;
+    /// Append a length prefixed UTF-8 string to `dst`, returning the number of bytes read.
+    fn get_string_utf8_into(&self, index: i32, dst: &mut String) -> Result<i32, BufferError>;
+    fn get_string_without_length_utf8(&self, index: i32, length: i32) -> Result<String, BufferError>;
+    /// View a length prefixed UTF-8 string in place. The view is only good until those bytes are
+    /// next written.
+    fn get_str_utf8(&self, index: i32) -> Result<&str, BufferError>;
+    fn get_str_without_length_utf8(&self, index: i32, length: i32) -> Result<&str, BufferError>;
+    /// Check that `index..index + length` lies within the capacity, whether or not the accessors
+    /// check their own bounds.
+    fn bounds_check(&self, index: i32, length: i32) -> Result<(), BufferError>;
     fn wrap_adjustment(&self) -> i32;
     fn is_expandable(&self) -> bool;
     fn set_memory(&self, index: i32, length: i32, value: u8);
     fn put_long(&mut self, index: i32, value: i64);
     fn put_int(&mut self, index: i32, value: i32);
+    /// Encode `value` as ASCII digits, with a leading '-' if negative, returning the length written.
     fn put_int_ascii(&self, index: i32, value: i32) -> i32;
-    fn put_natural_int_ascii(&self, index: i32, value: i32) -> i32;
-    fn put_natural_padding_int_ascii(&self, index: i32, length: i32, value: i64); // throws NumberFormatException
-    fn put_natural_int_ascii_from_end(&self, value: i32, end_exclusive: i32) -> i32;
-    fn put_natural_long_ascii(&self, index: i32, value: i64) -> i32;
+    /// Encode a natural number as ASCII digits, returning the length written. A negative `value`
+    /// is an error, as it is for the other `put_natural_*` methods.
+    fn put_natural_int_ascii(&self, index: i32, value: i32) -> Result<i32, AsciiNumberFormatError>;
+    /// Encode a natural number as exactly `length` ASCII digits, padded with leading zeros.
+    fn put_natural_padding_int_ascii(&self, index: i32, length: i32, value: i32) -> Result<(), AsciiNumberFormatError>;
+    /// Encode a natural number as ASCII digits ending just before `end_exclusive`, returning the
+    /// index at which the digits begin.
+    fn put_natural_int_ascii_from_end(&self, value: i32, end_exclusive: i32) -> Result<i32, AsciiNumberFormatError>;
+    fn put_natural_long_ascii(&self, index: i32, value: i64) -> Result<i32, AsciiNumberFormatError>;
     fn put_long_ascii(&self, index: i32, value: i64) -> i32;
     fn put_double(&self, index: i32, value: f64);
     fn put_float(&self, index: i32, value: f32);
     fn put_short(&self, index: i32, value: i16);
-    fn put_char(&self, index: i32, value: char);
+    fn put_char(&self, index: i32, value: u16);
+    fn put_short_be(&self, index: i32, value: i16);
+    fn put_short_le(&self, index: i32, value: i16);
+    fn put_int_be(&self, index: i32, value: i32);
+    fn put_int_le(&self, index: i32, value: i32);
+    fn put_long_be(&self, index: i32, value: i64);
+    fn put_long_le(&self, index: i32, value: i64);
+    fn put_float_be(&self, index: i32, value: f32);
+    fn put_float_le(&self, index: i32, value: f32);
+    fn put_double_be(&self, index: i32, value: f64);
+    fn put_double_le(&self, index: i32, value: f64);
     fn put_byte(&self, index: i32, value: u8);
-    fn put_bytes(&self, index: i32, bytes: &UnsafeBuffer);
+    fn put_bytes(&self, index: i32, bytes: &[u8]);
     // Im thinking of just screwing this, since rust doesn't have java's equivalent
     // fn put_bytes1(&self, index: i32, src_buffer: ByteBuffer, offset: i32, bytes: &[u8]);
     fn put_bytes2(&self, index: i32, src_buffer: &UnsafeBuffer, offset: i32, length: i32);
+    /// Put a string as ASCII prefixed with its length, one byte per char with chars outside ASCII
+    /// written as '?'. Returns the number of bytes written including the prefix.
     fn put_string_ascii(&self, index: i32, value: &str) -> i32;
     fn put_string_without_length_ascii(&self, index: i32, value: &str) -> i32;
+    /// Put up to `length` chars of `value` as ASCII, starting from char `value_offset`.
     fn put_string_without_length_ascii0(&self, index: i32, value: &str, value_offset: i32, length: i32) -> i32;
+    /// Put a string as UTF-8 prefixed with its encoded length, returning the number of bytes
+    /// written including the prefix.
     fn put_string_utf8(&self, index: i32, value: &str) -> i32;
-    fn put_string_utf8_1(&self, index: i32, value: &str, max_encoded_length: i32) -> i32;
+    /// As `put_string_utf8`, failing without writing anything if the encoded string is longer than
+    /// `max_encoded_length` bytes.
+    fn put_string_utf8_1(&self, index: i32, value: &str, max_encoded_length: i32) -> Result<i32, BufferError>;
     fn put_string_without_length_utf8(&self, index: i32, value: &str) -> i32;
 }
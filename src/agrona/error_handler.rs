@@ -0,0 +1,22 @@
+use crate::agrona::concurrent::agent::AgentError;
+
+/// Callback for errors that happen off the caller's thread, e.g. in an `AgentRunner`.
+pub trait ErrorHandler {
+    fn on_error(&mut self, error: &AgentError);
+}
+
+impl<F> ErrorHandler for F where F: FnMut(&AgentError) {
+    fn on_error(&mut self, error: &AgentError) {
+        self(error)
+    }
+}
+
+/// Error handler that prints errors to stderr.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoggingErrorHandler;
+
+impl ErrorHandler for LoggingErrorHandler {
+    fn on_error(&mut self, error: &AgentError) {
+        eprintln!("{}", error);
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+/// Directory used for shared memory files when it exists, e.g. `/dev/shm` on Linux.
+pub const SHM_DIR: &str = "/dev/shm";
+
+/// Size of a page of memory as reported by the operating system.
+pub fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+/// Directory to create memory mapped files in, preferring `/dev/shm` so the mapping is backed by
+/// memory rather than disk.
+pub fn tmp_dir_name() -> PathBuf {
+    let shm_dir = Path::new(SHM_DIR);
+    if shm_dir.is_dir() {
+        return shm_dir.to_path_buf();
+    }
+    std::env::temp_dir()
+}
+
+/// Create a new file of `length` zeroed bytes, truncating any existing file, and map it read-write
+/// and shared so other processes mapping the same file see the writes.
+pub fn map_new_file(path: &Path, length: usize) -> io::Result<*mut u8> {
+    let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+    file.set_len(length as u64)?;
+    map(&file, length)
+}
+
+/// Map an existing file read-write and shared, returning the address and the length of the file.
+pub fn map_existing_file(path: &Path) -> io::Result<(*mut u8, usize)> {
+    let file = OpenOptions::new().read(true).write(true).open(path)?;
+    let length = file.metadata()?.len() as usize;
+    if length == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("file is empty: {}", path.display())));
+    }
+    let address = map(&file, length)?;
+    Ok((address, length))
+}
+
+/// Unmap a region previously returned from `map_new_file` or `map_existing_file`.
+///
+/// # Safety
+/// `address` and `length` must describe a live mapping, which must not be accessed afterwards.
+pub unsafe fn unmap(address: *mut u8, length: usize) {
+    unsafe {
+        libc::munmap(address as *mut libc::c_void, length);
+    }
+}
+
+/// Unlink a file, e.g. once both sides of a shared ring buffer have mapped it. Existing mappings
+/// remain valid until unmapped.
+pub fn delete(path: &Path, ignore_failures: bool) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if !ignore_failures => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn map(file: &fs::File, length: usize) -> io::Result<*mut u8> {
+    let address = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            length,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+    if address == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(address as *mut u8)
+}
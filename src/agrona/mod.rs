@@ -1,2 +1,8 @@
+pub mod ascii_number_format_error;
+pub mod buffer_error;
 pub mod concurrent;
+pub mod deadline_timer_wheel;
 pub mod direct_buffer;
+pub mod error_handler;
+pub mod io_util;
+pub mod timer_wheel_error;
@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt;
+
+/// Errors from configuring or resetting a `DeadlineTimerWheel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerWheelError {
+    InvalidTicksPerWheel(i32),
+    InvalidTickResolution(i64),
+    InvalidTickAllocation(i32),
+    TimersActive(i64),
+}
+
+impl fmt::Display for TimerWheelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimerWheelError::InvalidTicksPerWheel(ticks_per_wheel) =>
+                write!(f, "ticks per wheel must be a power of 2: {}", ticks_per_wheel),
+            TimerWheelError::InvalidTickResolution(tick_resolution) =>
+                write!(f, "tick resolution must be a power of 2: {}", tick_resolution),
+            TimerWheelError::InvalidTickAllocation(tick_allocation) =>
+                write!(f, "tick allocation must be a power of 2: {}", tick_allocation),
+            TimerWheelError::TimersActive(timer_count) =>
+                write!(f, "can not reset startTime with active timers: timerCount={}", timer_count),
+        }
+    }
+}
+
+impl Error for TimerWheelError {}
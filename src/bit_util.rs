@@ -45,7 +45,7 @@ pub fn align(value: i32, alignment: i32) -> i32 {
 }
 
 pub fn from_hex_byte_array(buffer: &[u8]) -> Vec<u8> {
-    let mut output_buffer = vec![0u8; buffer.len() >> 1];
+    let mut output_buffer = Vec::with_capacity(buffer.len() >> 1);
     for i in (0..buffer.len()).step_by(2) {
         let hi = FROM_HEX_DIGIT_TABLE[buffer[i] as usize] << 4;
         let lo = FROM_HEX_DIGIT_TABLE[buffer[i + 1] as usize];
@@ -59,7 +59,7 @@ pub fn to_hex_byte_array(buffer: &[u8]) -> Vec<u8> {
 }
 
 pub fn to_hex_byte_array0(buffer: &[u8], offset: usize, length: usize) -> Vec<u8> {
-    let mut output_buffer = vec![0u8; buffer.len() << 1];
+    let mut output_buffer = vec![0u8; length << 1];
     for i in (0..(length << 1)).step_by(2) {
         let b = buffer[offset + (i >> 1)];
 
@@ -113,11 +113,11 @@ pub fn is_even0(value: i64) -> bool {
 }
 
 pub fn is_power_of_two_i32(value: i32) -> bool {
-    value > 0 && ((value & (value.wrapping_neg() + 1)) == value)
+    value > 0 && ((value & (!value + 1)) == value)
 }
 
 pub fn is_power_of_two_i320(value: i64) -> bool {
-    value > 0 && ((value & (value.wrapping_neg() + 1)) == value)
+    value > 0 && ((value & (!value + 1)) == value)
 }
 
 pub fn next(current: i32, max: i32) -> i32 {
@@ -0,0 +1,167 @@
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use Ringo::agrona::concurrent::agent::{Agent, AgentError};
+use Ringo::agrona::concurrent::agent_invoker::AgentInvoker;
+use Ringo::agrona::concurrent::agent_runner::AgentRunner;
+use Ringo::agrona::concurrent::composite_agent::CompositeAgent;
+use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer::RingBuffer;
+use Ringo::agrona::concurrent::sleeping_idle_strategy::SleepingIdleStrategy;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+struct CountingAgent {
+    role_name: &'static str,
+    events: Arc<Mutex<Vec<String>>>,
+    fail_on: i32,
+    terminate_on: i32,
+    cycles: i32,
+}
+
+impl CountingAgent {
+    fn new(role_name: &'static str, events: &Arc<Mutex<Vec<String>>>) -> Self {
+        CountingAgent { role_name, events: Arc::clone(events), fail_on: -1, terminate_on: -1, cycles: 0 }
+    }
+}
+
+impl Agent for CountingAgent {
+    fn on_start(&mut self) -> Result<(), AgentError> {
+        self.events.lock().unwrap().push(format!("{} start", self.role_name));
+        Ok(())
+    }
+
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        self.cycles += 1;
+        if self.cycles == self.terminate_on {
+            return Err(AgentError::Terminate);
+        }
+        if self.cycles == self.fail_on {
+            "x".parse::<i32>()?;
+        }
+        Ok(1)
+    }
+
+    fn on_close(&mut self) {
+        self.events.lock().unwrap().push(format!("{} close after {}", self.role_name, self.cycles));
+    }
+
+    fn role_name(&self) -> &str {
+        self.role_name
+    }
+}
+
+#[test]
+fn test_invoker_drives_agent_manually() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut errors = Vec::new();
+    let mut agent = CountingAgent::new("counter", &events);
+    agent.fail_on = 2;
+    agent.terminate_on = 4;
+    let mut invoker = AgentInvoker::new(|e: &AgentError| errors.push(e.to_string()), agent);
+
+    assert_eq!(0, invoker.invoke());
+    invoker.start();
+    assert!(invoker.is_running());
+    assert_eq!(vec![1, 0, 1, 0, 0], (0..5).map(|_| invoker.invoke()).collect::<Vec<_>>());
+    assert!(invoker.is_closed());
+    invoker.close();
+    drop(invoker);
+
+    assert_eq!(vec!["invalid digit found in string"], errors);
+    assert_eq!(vec!["counter start", "counter close after 4"], *events.lock().unwrap());
+}
+
+#[test]
+fn test_composite_agent_runs_all_agents() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut second = CountingAgent::new("b", &events);
+    second.fail_on = 2;
+    let mut composite = CompositeAgent::new(vec![
+        Box::new(CountingAgent::new("a", &events)),
+        Box::new(second),
+        Box::new(CountingAgent::new("c", &events)),
+    ]);
+
+    assert_eq!("[a,b,c]", composite.role_name());
+    composite.on_start().unwrap();
+    assert_eq!(3, composite.do_work().unwrap());
+    assert!(composite.do_work().is_err());
+    assert_eq!(1, composite.do_work().unwrap());
+    assert_eq!(3, composite.do_work().unwrap());
+    composite.on_close();
+
+    assert_eq!(vec!["a start", "b start", "c start", "a close after 3", "b close after 3", "c close after 3"], *events.lock().unwrap());
+}
+
+struct EchoAgent {
+    ring_buffer: Arc<OneToOneRingBuffer>,
+    sender: mpsc::Sender<i64>,
+}
+
+impl Agent for EchoAgent {
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        let sender = &self.sender;
+        Ok(self.ring_buffer.read(|_, buffer, index, _| sender.send(buffer.get_long(index)).unwrap()))
+    }
+
+    fn role_name(&self) -> &str {
+        "echo"
+    }
+}
+
+#[test]
+fn test_runner_consumes_on_its_own_thread() {
+    let ring_buffer = Arc::new(OneToOneRingBuffer::new(UnsafeBuffer::new(1024)));
+    let (sender, receiver) = mpsc::channel();
+    let agent = EchoAgent { ring_buffer: Arc::clone(&ring_buffer), sender };
+    let mut runner = AgentRunner::start_on_thread(agent, SleepingIdleStrategy::new(100_000), |e: &AgentError| panic!("{}", e)).unwrap();
+    assert_eq!("echo", runner.role_name());
+
+    for value in 0..10i64 {
+        assert!(ring_buffer.write_vectored(1, &[&value.to_ne_bytes()]));
+        assert_eq!(value, receiver.recv().unwrap());
+    }
+
+    assert!(runner.is_running());
+    runner.close().unwrap();
+    assert!(!runner.is_running());
+    assert!(runner.is_closed());
+}
+
+#[test]
+fn test_runner_stops_when_agent_terminates() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut agent = CountingAgent::new("terminating", &events);
+    agent.terminate_on = 3;
+    let mut runner = AgentRunner::start_on_thread(agent, SleepingIdleStrategy::default(), |_: &AgentError| {}).unwrap();
+
+    while runner.is_running() {
+        std::thread::yield_now();
+    }
+    runner.close().unwrap();
+    assert_eq!(vec!["terminating start", "terminating close after 3"], *events.lock().unwrap());
+}
+
+struct PanickingAgent;
+
+impl Agent for PanickingAgent {
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        panic!("agent failed");
+    }
+
+    fn role_name(&self) -> &str {
+        "panicking"
+    }
+}
+
+#[test]
+fn test_runner_returns_agent_panic_from_close() {
+    let mut runner = AgentRunner::start_on_thread(PanickingAgent, SleepingIdleStrategy::default(), |_: &AgentError| {}).unwrap();
+
+    while runner.is_running() {
+        std::thread::yield_now();
+    }
+    let payload = runner.close().unwrap_err();
+    assert_eq!(Some(&"agent failed"), payload.downcast_ref::<&str>());
+    assert!(runner.close().is_ok());
+}
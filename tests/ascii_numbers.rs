@@ -0,0 +1,73 @@
+use Ringo::agrona::ascii_number_format_error::AsciiNumberFormatError;
+use Ringo::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+fn ascii(buffer: &UnsafeBuffer, index: i32, length: i32) -> String {
+    (index..index + length).map(|i| buffer.get_byte_volatile(i) as char).collect()
+}
+
+#[test]
+fn test_put_and_parse_round_trip() {
+    let buffer = UnsafeBuffer::new(64);
+
+    for value in [0, 7, -7, 1234567890, i32::MAX, i32::MIN] {
+        let length = buffer.put_int_ascii(4, value);
+        assert_eq!(value.to_string(), ascii(&buffer, 4, length));
+        assert_eq!(Ok(value), buffer.parse_int_ascii(4, length));
+    }
+
+    for value in [0, 42, -42, i64::MAX, i64::MIN] {
+        let length = buffer.put_long_ascii(4, value);
+        assert_eq!(value.to_string(), ascii(&buffer, 4, length));
+        assert_eq!(Ok(value), buffer.parse_long_ascii(4, length));
+    }
+
+    let length = buffer.put_natural_long_ascii(0, 9876543210).unwrap();
+    assert_eq!(Ok(9876543210), buffer.parse_natural_long_ascii(0, length));
+    let length = buffer.put_natural_int_ascii(0, 35).unwrap();
+    assert_eq!(Ok(35), buffer.parse_natural_int_ascii(0, length));
+}
+
+#[test]
+fn test_put_natural_ascii_from_end_and_padded() {
+    let buffer = UnsafeBuffer::new(64);
+
+    assert_eq!(Ok(7), buffer.put_natural_int_ascii_from_end(123, 10));
+    assert_eq!("123", ascii(&buffer, 7, 3));
+
+    assert_eq!(Ok(()), buffer.put_natural_padding_int_ascii(0, 6, 52));
+    assert_eq!("000052", ascii(&buffer, 0, 6));
+    assert_eq!(Err(AsciiNumberFormatError::ValueTooLong { value: 1234, length: 3 }), buffer.put_natural_padding_int_ascii(0, 3, 1234));
+    assert_eq!(Err(AsciiNumberFormatError::NegativeValue(-1)), buffer.put_natural_padding_int_ascii(0, 3, -1));
+}
+
+#[test]
+fn test_parse_errors() {
+    let buffer = UnsafeBuffer::new(64);
+
+    let length = buffer.put_long_ascii(0, i32::MAX as i64 + 1);
+    assert_eq!(Err(AsciiNumberFormatError::Overflow { index: 0, length }), buffer.parse_int_ascii(0, length));
+    assert_eq!(Err(AsciiNumberFormatError::Overflow { index: 0, length }), buffer.parse_natural_int_ascii(0, length));
+
+    buffer.set_memory(0, 20, b'9');
+    assert_eq!(Err(AsciiNumberFormatError::Overflow { index: 0, length: 20 }), buffer.parse_long_ascii(0, 20));
+
+    buffer.put_byte(5, b'x');
+    assert_eq!(Err(AsciiNumberFormatError::InvalidDigit { index: 5, value: b'x' }), buffer.parse_natural_long_ascii(0, 8));
+
+    buffer.put_byte(0, b'-');
+    assert_eq!(Err(AsciiNumberFormatError::InvalidDigit { index: 0, value: b'-' }), buffer.parse_int_ascii(0, 1));
+    assert_eq!(Err(AsciiNumberFormatError::InvalidDigit { index: 0, value: b'-' }), buffer.parse_natural_int_ascii(0, 3));
+    assert_eq!(Err(AsciiNumberFormatError::Empty { index: 0, length: 0 }), buffer.parse_long_ascii(0, 0));
+}
+
+#[test]
+fn test_put_natural_rejects_negative() {
+    let buffer = UnsafeBuffer::new(16);
+
+    assert_eq!(Err(AsciiNumberFormatError::NegativeValue(-5)), buffer.put_natural_int_ascii(0, -5));
+    assert_eq!(Err(AsciiNumberFormatError::NegativeValue(i64::MIN)), buffer.put_natural_long_ascii(0, i64::MIN));
+    assert_eq!(Err(AsciiNumberFormatError::NegativeValue(-1)), buffer.put_natural_int_ascii_from_end(-1, 8));
+    assert_eq!(0, buffer.get_byte(0));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "async")]
+
+use futures_util::StreamExt;
+use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_receives_messages_written_while_full() {
+    let (mut producer, mut consumer) = OneToOneRingBuffer::new(UnsafeBuffer::new(256)).split_async();
+
+    // 1000 messages of 16 bytes through 256 bytes of capacity, so the producer waits on the consumer
+    let writer = tokio::spawn(async move {
+        for value in 0..1000i64 {
+            if value % 2 == 0 {
+                producer.write(1, &value.to_ne_bytes()).await.unwrap();
+            } else {
+                let mut claim = producer.claim(2, 8).await.unwrap();
+                claim.copy_from_slice(&value.to_ne_bytes());
+                claim.commit().unwrap();
+            }
+        }
+    });
+
+    for value in 0..1000i64 {
+        let (msg_type_id, bytes) = consumer.next().await.unwrap();
+        assert_eq!(1 + value as i32 % 2, msg_type_id);
+        assert_eq!(value.to_ne_bytes().to_vec(), bytes);
+    }
+    writer.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_waits_for_producer() {
+    let (mut producer, mut consumer) = OneToOneRingBuffer::new(UnsafeBuffer::new(1024)).split_async();
+
+    let reader = tokio::spawn(async move {
+        let mut values = Vec::new();
+        while values.len() < 3 {
+            consumer.read(|_, buffer, index, _| values.push(buffer.get_long(index))).await;
+        }
+        values
+    });
+
+    for value in 0..3i64 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        producer.write(1, &value.to_ne_bytes()).await.unwrap();
+    }
+    assert_eq!(vec![0, 1, 2], reader.await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_ends_after_producer_dropped() {
+    let (mut producer, consumer) = OneToOneRingBuffer::new(UnsafeBuffer::new(256)).split_async();
+
+    let writer = tokio::spawn(async move {
+        for value in 0..100i64 {
+            producer.write(1, &value.to_ne_bytes()).await.unwrap();
+        }
+    });
+
+    let values: Vec<i64> = consumer.map(|(_, bytes)| i64::from_ne_bytes(bytes.try_into().unwrap())).collect().await;
+    assert_eq!((0..100).collect::<Vec<_>>(), values);
+    writer.await.unwrap();
+}
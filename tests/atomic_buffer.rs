@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::thread;
+use Ringo::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+
+#[test]
+fn test_long_read_modify_write() {
+    let buffer = UnsafeBuffer::new(64);
+
+    assert_eq!(0, buffer.get_and_set_long(8, 5));
+    assert_eq!(5, buffer.get_and_add_long(8, 3));
+    buffer.add_long_ordered(8, 2);
+    assert!(!buffer.compare_and_set_long(8, 9, 20));
+    assert!(buffer.compare_and_set_long(8, 10, 20));
+    assert_eq!(20, buffer.get_long_volatile(8));
+}
+
+#[test]
+fn test_add_long_ordered_wraps() {
+    let buffer = UnsafeBuffer::new(64);
+
+    buffer.get_and_set_long(8, i64::MAX);
+    buffer.add_long_ordered(8, 1);
+    assert_eq!(i64::MIN, buffer.get_long_volatile(8));
+}
+
+#[test]
+fn test_int_read_modify_write() {
+    let buffer = UnsafeBuffer::new(64);
+
+    assert_eq!(0, buffer.get_and_set_int(4, -7));
+    assert_eq!(-7, buffer.get_and_add_int(4, 10));
+    buffer.add_int_ordered(4, 1);
+    assert!(!buffer.compare_and_set_int(4, 3, 40));
+    assert!(buffer.compare_and_set_int(4, 4, 40));
+    assert_eq!(40, buffer.get_int_volatile(4));
+    assert_eq!(0, buffer.get_int_volatile(0));
+    assert_eq!(0, buffer.get_int_volatile(8));
+}
+
+#[test]
+fn test_short_char_and_byte_volatile() {
+    let buffer = UnsafeBuffer::new(64);
+
+    buffer.put_short_volatile(2, -2);
+    assert_eq!(-2, buffer.get_short_volatile(2));
+    assert_eq!(0, buffer.get_short_volatile(0));
+
+    buffer.put_char_volatile(4, 'é' as u16);
+    assert_eq!('é' as u16, buffer.get_char_volatile(4));
+    assert_eq!(0xE9, buffer.get_short_volatile(4));
+
+    // a char outside the Basic Multilingual Plane is stored as a surrogate pair, as in Java
+    let mut surrogates = [0u16; 2];
+    '🦀'.encode_utf16(&mut surrogates);
+    buffer.put_char_volatile(6, surrogates[0]);
+    buffer.put_char_volatile(8, surrogates[1]);
+    let units = [buffer.get_char_volatile(6), buffer.get_char_volatile(8)];
+    assert_eq!(Some(Ok('🦀')), char::decode_utf16(units).next());
+
+    buffer.put_byte_ordered(11, 0xAB);
+    assert_eq!(0xAB, buffer.get_byte_volatile(11));
+    assert_eq!(0, buffer.get_byte_volatile(10));
+}
+
+#[test]
+fn test_add_int_ordered_wraps() {
+    let buffer = UnsafeBuffer::new(64);
+
+    buffer.get_and_set_int(4, i32::MAX);
+    buffer.add_int_ordered(4, 1);
+    assert_eq!(i32::MIN, buffer.get_int_volatile(4));
+}
+
+// only the atomic accessors are used from the threads sharing it
+struct SharedBuffer(UnsafeBuffer);
+
+unsafe impl Send for SharedBuffer {}
+
+unsafe impl Sync for SharedBuffer {}
+
+#[test]
+fn test_contended_get_and_add_and_compare_and_set() {
+    const THREADS: i32 = 4;
+    const INCREMENTS: i32 = 10_000;
+    let shared = Arc::new(SharedBuffer(UnsafeBuffer::new(64)));
+
+    let handles: Vec<_> = (0..THREADS).map(|_| {
+        let shared = Arc::clone(&shared);
+        thread::spawn(move || {
+            let buffer = &shared.0;
+            for _ in 0..INCREMENTS {
+                buffer.get_and_add_int(0, 1);
+                loop {
+                    let value = buffer.get_long_volatile(8);
+                    if buffer.compare_and_set_long(8, value, value + 2) {
+                        break;
+                    }
+                }
+            }
+        })
+    }).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let buffer = &shared.0;
+    assert_eq!(THREADS * INCREMENTS, buffer.get_int_volatile(0));
+    assert_eq!(2 * (THREADS * INCREMENTS) as i64, buffer.get_long_volatile(8));
+}
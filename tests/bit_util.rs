@@ -0,0 +1,24 @@
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_descriptor::{check_capacity, TRAILER_LENGTH};
+use Ringo::bit_util::{from_hex, is_power_of_two_i32, to_hex, to_hex0};
+
+#[test]
+fn test_to_hex_of_slice() {
+    let bytes = [0x01u8, 0xAB, 0xFF, 0x10];
+
+    assert_eq!("01abff10", to_hex0(&bytes));
+    assert_eq!("abff", to_hex(&bytes, 1, 2));
+    assert_eq!(bytes.to_vec(), from_hex("01abff10"));
+}
+
+#[test]
+fn test_is_power_of_two_i32() {
+    for shift in 0..31 {
+        assert!(is_power_of_two_i32(1 << shift));
+    }
+    for value in [0, -1, -1024, i32::MIN, 3, 6, 1000, 1023, 1025, i32::MAX] {
+        assert!(!is_power_of_two_i32(value), "{}", value);
+    }
+
+    assert_eq!(Ok(1024), check_capacity(1024 + TRAILER_LENGTH, 8));
+    assert!(check_capacity(1000 + TRAILER_LENGTH, 8).is_err());
+}
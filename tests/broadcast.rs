@@ -0,0 +1,124 @@
+use Ringo::agrona::concurrent::broadcast::broadcast_buffer_descriptor::TRAILER_LENGTH;
+use Ringo::agrona::concurrent::broadcast::broadcast_error::BroadcastError;
+use Ringo::agrona::concurrent::broadcast::broadcast_transmitter::BroadcastTransmitter;
+use Ringo::agrona::concurrent::broadcast::copy_broadcast_receiver::CopyBroadcastReceiver;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+const CAPACITY: i32 = 1024;
+
+fn transmit_long(transmitter: &mut BroadcastTransmitter, msg_type_id: i32, value: i64) {
+    transmit_longs(transmitter, msg_type_id, &[value]);
+}
+
+fn transmit_longs(transmitter: &mut BroadcastTransmitter, msg_type_id: i32, values: &[i64]) {
+    let length = values.len() as i32 * 8;
+    let mut src = UnsafeBuffer::allocate_direct(length as usize);
+    for (i, value) in values.iter().enumerate() {
+        src.put_long(i as i32 * 8, *value);
+    }
+    transmitter.transmit(msg_type_id, &src, 0, length).unwrap();
+}
+
+#[test]
+fn test_receivers_see_every_message() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let mut receivers = vec![transmitter.new_receiver(), transmitter.new_receiver()];
+
+    // 24 byte records do not divide the capacity, so wrapping leaves a padding record
+    for round in 0..4 {
+        for i in 0..20 {
+            transmit_longs(&mut transmitter, 7, &[round * 100 + i, -1]);
+        }
+        for receiver in receivers.iter_mut() {
+            for i in 0..20 {
+                assert!(receiver.receive_next());
+                assert_eq!(7, receiver.type_id());
+                assert_eq!(16, receiver.length());
+                assert_eq!(round * 100 + i, receiver.buffer().get_long(receiver.offset()));
+                assert!(receiver.validate());
+            }
+            assert!(!receiver.receive_next());
+        }
+    }
+    assert_eq!(0, receivers[0].lapped_count());
+}
+
+#[test]
+fn test_lapped_receiver_skips_to_latest() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let mut receiver = transmitter.new_receiver();
+
+    for i in 0..200 {
+        transmit_long(&mut transmitter, 1, i);
+    }
+
+    assert!(receiver.receive_next());
+    assert_eq!(1, receiver.lapped_count());
+    assert_eq!(199, receiver.buffer().get_long(receiver.offset()));
+    assert!(!receiver.receive_next());
+}
+
+#[test]
+fn test_copy_receiver_copies_into_scratch_buffer() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    transmit_long(&mut transmitter, 1, 1);
+    let mut receiver = CopyBroadcastReceiver::new(transmitter.new_receiver(), UnsafeBuffer::allocate_direct(64));
+
+    transmit_long(&mut transmitter, 2, 42);
+    let mut received = Vec::new();
+    assert_eq!(Ok(1), receiver.receive(|msg_type_id, buffer, index, length| {
+        received.push((msg_type_id, index, length, buffer.get_long(index)));
+    }));
+    assert_eq!(vec![(2, 0, 8, 42)], received);
+    assert_eq!(Ok(0), receiver.receive(|_, _, _, _| {}));
+}
+
+#[test]
+fn test_copy_receiver_reports_lapped_and_recovers() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let mut receiver = CopyBroadcastReceiver::new(transmitter.new_receiver(), UnsafeBuffer::allocate_direct(64));
+
+    for i in 0..200 {
+        transmit_long(&mut transmitter, 1, i);
+    }
+    assert_eq!(Err(BroadcastError::Lapped), receiver.receive(|_, _, _, _| {}));
+    assert_eq!(1, receiver.lapped_count());
+
+    transmit_long(&mut transmitter, 2, 7);
+    let mut received = Vec::new();
+    assert_eq!(Ok(1), receiver.receive(|msg_type_id, buffer, index, _| received.push((msg_type_id, buffer.get_long(index)))));
+    assert_eq!(vec![(2, 7)], received);
+}
+
+#[test]
+fn test_copy_receiver_reports_scratch_too_small() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let mut receiver = CopyBroadcastReceiver::new(transmitter.new_receiver(), UnsafeBuffer::allocate_direct(8));
+
+    transmit_longs(&mut transmitter, 1, &[1, 2]);
+    assert_eq!(Err(BroadcastError::ScratchTooSmall { length: 16, capacity: 8 }), receiver.receive(|_, _, _, _| {}));
+}
+
+#[test]
+fn test_transmit_errors() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let src = UnsafeBuffer::allocate_direct(256);
+
+    assert_eq!(Err(BroadcastError::InvalidTypeId(0)), transmitter.transmit(0, &src, 0, 8));
+    assert_eq!(Err(BroadcastError::InvalidMessageLength(-1)), transmitter.transmit(1, &src, 0, -1));
+    assert_eq!(Err(BroadcastError::MessageTooLong { length: 129, max_msg_length: 128 }), transmitter.transmit(1, &src, 0, 129));
+    assert_eq!(Ok(()), transmitter.transmit(1, &src, 0, 128));
+}
+
+#[test]
+fn test_receiver_outlives_transmitter() {
+    let mut transmitter = BroadcastTransmitter::new(UnsafeBuffer::allocate_direct((CAPACITY + TRAILER_LENGTH) as usize));
+    let mut receiver = transmitter.new_receiver();
+    transmit_long(&mut transmitter, 3, 99);
+    drop(transmitter);
+
+    assert!(receiver.receive_next());
+    assert_eq!(3, receiver.type_id());
+    assert_eq!(99, receiver.buffer().get_long(receiver.offset()));
+}
@@ -0,0 +1,85 @@
+use Ringo::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+#[test]
+fn test_big_and_little_endian_accessors() {
+    let buffer = UnsafeBuffer::new(64);
+
+    // network order length field followed by a little-endian sequence number, unaligned
+    buffer.put_int_be(1, 0x0102_0304);
+    buffer.put_long_le(5, 0x0a0b_0c0d_0e0f_1011);
+    let mut bytes = [0u8; 12];
+    buffer.get_bytes(1, &mut bytes);
+    assert_eq!([1, 2, 3, 4, 0x11, 0x10, 0x0f, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a], bytes);
+
+    assert_eq!(0x0102_0304, buffer.get_int_be(1));
+    assert_eq!(0x0403_0201, buffer.get_int_le(1));
+    assert_eq!(0x0a0b_0c0d_0e0f_1011, buffer.get_long_le(5));
+    assert_eq!(0x1110_0f0e_0d0c_0b0a, buffer.get_long_be(5));
+
+    buffer.put_short_be(20, -2);
+    assert_eq!([0xff, 0xfe], [buffer.get_byte(20), buffer.get_byte(21)]);
+    assert_eq!(-2, buffer.get_short_be(20));
+    buffer.put_short_le(20, -2);
+    assert_eq!(-2, buffer.get_short_le(20));
+
+    buffer.put_float_be(24, 1.5);
+    buffer.put_double_le(28, -0.25);
+    assert_eq!(1.5, buffer.get_float_be(24));
+    assert_eq!(-0.25, buffer.get_double_le(28));
+    buffer.put_float_le(24, 2.5);
+    buffer.put_double_be(28, 8.0);
+    assert_eq!(2.5, buffer.get_float_le(24));
+    assert_eq!(8.0, buffer.get_double_be(28));
+}
+
+#[test]
+fn test_native_order_accessors() {
+    let buffer = UnsafeBuffer::new(64);
+
+    buffer.put_short(1, 513);
+    buffer.put_char(3, 'é' as u16);
+    buffer.put_float(5, 3.25);
+    buffer.put_double(9, 6.5);
+    assert_eq!(513, buffer.get_short(1));
+    assert_eq!('é' as u16, buffer.get_char(3));
+    assert_eq!(3.25, buffer.get_float(5));
+    assert_eq!(6.5, buffer.get_double(9));
+    assert_eq!(buffer.get_short(1), if cfg!(target_endian = "little") { buffer.get_short_le(1) } else { buffer.get_short_be(1) });
+}
+
+#[test]
+fn test_bounds_check() {
+    let buffer = UnsafeBuffer::allocate_direct(16);
+
+    assert!(buffer.bounds_check(8, 8).is_ok());
+    assert!(buffer.bounds_check(9, 8).is_err());
+    assert!(buffer.bounds_check(-1, 1).is_err());
+    assert!(buffer.bounds_check(i32::MAX - 1, 8).is_err());
+    assert!(buffer.bounds_check(i32::MAX, i32::MAX).is_err());
+}
+
+#[cfg(any(debug_assertions, feature = "bounds-checks"))]
+#[test]
+#[should_panic(expected = "index=2147483646 length=8 capacity=16")]
+fn test_accessor_near_i32_max_panics() {
+    let buffer = UnsafeBuffer::allocate_direct(16);
+    buffer.get_long_le(i32::MAX - 1);
+}
+
+#[cfg(any(debug_assertions, feature = "bounds-checks"))]
+#[test]
+#[should_panic(expected = "index=12 length=8 capacity=16")]
+fn test_accessor_beyond_capacity_panics() {
+    let buffer = UnsafeBuffer::allocate_direct(16);
+    buffer.get_long_volatile(12);
+}
+
+#[cfg(any(debug_assertions, feature = "bounds-checks"))]
+#[test]
+#[should_panic(expected = "index=14 length=4 capacity=16")]
+fn test_unaligned_accessor_beyond_capacity_panics() {
+    let buffer = UnsafeBuffer::allocate_direct(16);
+    buffer.put_int_be(14, 1);
+}
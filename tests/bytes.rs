@@ -0,0 +1,77 @@
+use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer::RingBuffer;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+#[test]
+fn test_bytes_to_and_from_slices() {
+    let buffer = UnsafeBuffer::new(64);
+
+    buffer.put_bytes(3, b"payload");
+    buffer.put_byte(10, b'!');
+    assert_eq!(b'y', buffer.get_byte(5));
+
+    let mut dst = [0u8; 8];
+    buffer.get_bytes(3, &mut dst);
+    assert_eq!(b"payload!", &dst);
+
+    let mut dst = [b'.'; 6];
+    buffer.get_bytes0(6, &mut dst, 1, 4);
+    assert_eq!(b".load.", &dst);
+
+    buffer.set_memory(4, 3, b'-');
+    buffer.get_bytes(3, &mut dst);
+    assert_eq!(b"p---oa", &dst);
+}
+
+#[test]
+fn test_bytes_between_buffers() {
+    let src = UnsafeBuffer::new(64);
+    let dst = UnsafeBuffer::new(64);
+    src.put_bytes(0, b"abcdefgh");
+
+    src.get_bytes1(2, &dst, 10, 4);
+    dst.put_bytes2(20, &src, 0, 3);
+    assert_eq!(Ok("cdef".to_string()), dst.get_string_without_length_ascii(10, 4));
+    assert_eq!(Ok("abc".to_string()), dst.get_string_without_length_ascii(20, 3));
+
+    // overlapping copy within the same buffer
+    src.put_bytes2(2, &src, 0, 6);
+    assert_eq!(Ok("ababcdef".to_string()), src.get_string_without_length_ascii(0, 8));
+}
+
+#[test]
+fn test_zero_capacity_buffer() {
+    let buffer = UnsafeBuffer::allocate_direct(0);
+    assert_eq!(0, buffer.capacity());
+    assert_eq!(Ok(()), buffer.bounds_check(0, 0));
+    assert!(buffer.bounds_check(0, 1).is_err());
+
+    buffer.put_bytes(0, &[]);
+    buffer.get_bytes(0, &mut []);
+}
+
+#[test]
+#[should_panic]
+fn test_get_bytes0_rejects_range_beyond_slice() {
+    let buffer = UnsafeBuffer::new(64);
+    buffer.get_bytes0(0, &mut [0u8; 4], 2, 4);
+}
+
+#[test]
+fn test_write_arbitrary_payload() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(4096));
+    let payload: Vec<u8> = (0..=255).collect();
+    let src = UnsafeBuffer::new(payload.len());
+    src.put_bytes(0, &payload);
+
+    assert!(ring_buffer.write(7, &src, 0, payload.len() as i32));
+
+    let mut received = Vec::new();
+    ring_buffer.read(|msg_type_id, buffer, index, length| {
+        assert_eq!(7, msg_type_id);
+        received.resize(length as usize, 0);
+        buffer.get_bytes(index, &mut received);
+    });
+    assert_eq!(payload, received);
+}
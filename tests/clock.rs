@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use Ringo::agrona::concurrent::agent::Agent;
+use Ringo::agrona::concurrent::cached_clock_updater::CachedClockUpdater;
+use Ringo::agrona::concurrent::cached_epoch_clock::CachedEpochClock;
+use Ringo::agrona::concurrent::cached_nano_clock::CachedNanoClock;
+use Ringo::agrona::concurrent::epoch_clock::{EpochClock, SystemEpochClock};
+use Ringo::agrona::concurrent::nano_clock::{NanoClock, SystemNanoClock};
+use Ringo::agrona::concurrent::test_clock::TestClock;
+
+#[test]
+fn test_cached_clocks_updated_by_agent() {
+    let epoch_clock = Arc::new(CachedEpochClock::new());
+    let nano_clock = Arc::new(CachedNanoClock::new());
+    let before_ms = SystemEpochClock.time();
+    let mut updater = CachedClockUpdater::new(Arc::clone(&epoch_clock), Arc::clone(&nano_clock));
+
+    let time_ms = epoch_clock.time();
+    let nano_time = nano_clock.nano_time();
+    assert!(time_ms >= before_ms);
+    assert!(nano_time <= SystemNanoClock.nano_time());
+    assert_eq!(nano_time, nano_clock.nano_time());
+
+    assert_eq!(0, updater.do_work().unwrap());
+    assert!(nano_clock.nano_time() > nano_time);
+    assert!(epoch_clock.time() >= time_ms);
+
+    epoch_clock.update(5);
+    epoch_clock.advance(2);
+    assert_eq!(7, epoch_clock.time());
+}
+
+#[test]
+fn test_clock_only_moves_when_advanced() {
+    let clock = TestClock::new(10);
+    assert_eq!(10, clock.time());
+    assert_eq!(10_000_000, clock.nano_time());
+
+    clock.advance_ns(1_500_000);
+    assert_eq!(11, clock.time());
+    assert_eq!(11_500_000, clock.nano_time());
+
+    clock.advance_ms(4);
+    clock.set_time(clock.time() + 1);
+    assert_eq!(16, clock.time());
+    assert_eq!(16_000_000, clock.nano_time());
+}
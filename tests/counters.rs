@@ -0,0 +1,151 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use Ringo::agrona::concurrent::status::atomic_counter::AtomicCounter;
+use Ringo::agrona::concurrent::status::counters_error::CountersError;
+use Ringo::agrona::concurrent::status::counters_manager::CountersManager;
+use Ringo::agrona::concurrent::status::counters_reader::*;
+use Ringo::agrona::concurrent::status::position::Position;
+use Ringo::agrona::concurrent::status::unsafe_buffer_position::UnsafeBufferPosition;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+
+const NUMBER_OF_COUNTERS: usize = 4;
+
+fn new_manager(now_ms: &Arc<AtomicI64>, free_to_reuse_timeout_ms: i64) -> CountersManager {
+    let now_ms = Arc::clone(now_ms);
+    CountersManager::with_free_to_reuse_timeout(
+        UnsafeBuffer::allocate_direct(NUMBER_OF_COUNTERS * METADATA_LENGTH as usize),
+        UnsafeBuffer::allocate_direct(NUMBER_OF_COUNTERS * COUNTER_LENGTH as usize),
+        move || now_ms.load(Ordering::Relaxed),
+        free_to_reuse_timeout_ms)
+}
+
+#[test]
+fn test_layout_matches_agrona() {
+    assert_eq!(128, COUNTER_LENGTH);
+    assert_eq!(512, METADATA_LENGTH);
+    assert_eq!(112, MAX_KEY_LENGTH);
+    assert_eq!(380, MAX_LABEL_LENGTH);
+}
+
+#[test]
+fn test_allocate_and_read_metadata() {
+    let mut manager = new_manager(&Arc::new(AtomicI64::new(0)), 0);
+    assert_eq!(3, manager.max_counter_id());
+
+    assert_eq!(Ok(0), manager.allocate("messages read"));
+    assert_eq!(Ok(1), manager.allocate_with_key("buffer full", 7, &42i64.to_ne_bytes()));
+    manager.set_counter_value(1, 5).unwrap();
+    manager.set_counter_registration_id(1, 99).unwrap();
+
+    let reader = manager.reader();
+    let mut counters = Vec::new();
+    reader.for_each(|counter_id, type_id, key, label| counters.push((counter_id, type_id, key[..8].to_vec(), label.to_string())));
+    assert_eq!(vec![
+        (0, DEFAULT_TYPE_ID, vec![0; 8], "messages read".to_string()),
+        (1, 7, 42i64.to_ne_bytes().to_vec(), "buffer full".to_string()),
+    ], counters);
+    assert_eq!(Ok(5), reader.get_counter_value(1));
+    assert_eq!(Ok(99), reader.get_counter_registration_id(1));
+
+    let long_label = "x".repeat(500);
+    let counter_id = manager.allocate(&long_label).unwrap();
+    assert_eq!(MAX_LABEL_LENGTH as usize, manager.get_counter_label(counter_id).unwrap().len());
+    assert_eq!(Err(CountersError::KeyTooLong { length: 113, max_key_length: 112 }), manager.allocate_with_key("key", 1, &[0; 113]));
+}
+
+#[test]
+fn test_freed_counters_reused_after_timeout() {
+    let now_ms = Arc::new(AtomicI64::new(1000));
+    let mut manager = new_manager(&now_ms, 100);
+    for i in 0..NUMBER_OF_COUNTERS {
+        manager.allocate(&format!("counter {}", i)).unwrap();
+    }
+    assert_eq!(0, manager.available_counter_capacity());
+    assert_eq!(Err(CountersError::BufferFull { max_counter_id: 3 }), manager.allocate("full"));
+
+    manager.set_counter_value(2, 10).unwrap();
+    manager.free(2).unwrap();
+    assert_eq!(Ok(RECORD_RECLAIMED), manager.get_counter_state(2));
+    assert_eq!(Ok(1100), manager.get_free_for_reuse_deadline(2));
+    assert_eq!(Err(CountersError::CounterNotAllocated(2)), manager.free(2));
+    assert_eq!(0, manager.available_counter_capacity());
+    assert!(manager.allocate("too soon").is_err());
+
+    now_ms.store(1100, Ordering::Relaxed);
+    assert_eq!(1, manager.available_counter_capacity());
+    assert_eq!(Ok(2), manager.allocate("reused"));
+    assert_eq!(Ok(0), manager.get_counter_value(2));
+    assert_eq!(Ok("reused".to_string()), manager.get_counter_label(2));
+}
+
+#[test]
+fn test_counter_and_position_handles() {
+    let mut manager = new_manager(&Arc::new(AtomicI64::new(0)), 0);
+    let counter = Arc::new(manager.new_counter("messages read").unwrap());
+    assert_eq!("messages read", counter.label());
+
+    let threads: Vec<_> = (0..4).map(|_| {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || {
+            for _ in 0..10_000 {
+                counter.increment();
+            }
+        })
+    }).collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(Ok(40_000), manager.get_counter_value(counter.id()));
+    assert_eq!(40_000, counter.increment_ordered());
+    assert!(counter.compare_and_set(40_001, 7));
+    assert!(!counter.propose_max(6));
+    assert_eq!(7, counter.get());
+
+    let position = UnsafeBufferPosition::new(manager.reader(), manager.allocate("position").unwrap()).unwrap();
+    position.set_ordered(64);
+    assert!(position.propose_max_ordered(128));
+    assert!(!position.propose_max(96));
+    assert_eq!(Ok(128), manager.get_counter_value(position.id()));
+}
+
+#[test]
+fn test_counter_id_out_of_range() {
+    let mut manager = new_manager(&Arc::new(AtomicI64::new(0)), 0);
+    let invalid = |counter_id| CountersError::InvalidCounterId { counter_id, max_counter_id: 3 };
+
+    for counter_id in [-1, 4, i32::MAX] {
+        assert_eq!(Err(invalid(counter_id)), manager.get_counter_value(counter_id));
+        assert_eq!(Err(invalid(counter_id)), manager.get_counter_state(counter_id));
+        assert_eq!(Err(invalid(counter_id)), manager.get_counter_type_id(counter_id));
+        assert_eq!(Err(invalid(counter_id)), manager.get_counter_key(counter_id));
+        assert_eq!(Err(invalid(counter_id)), manager.get_counter_label(counter_id));
+        assert_eq!(Err(invalid(counter_id)), manager.set_counter_value(counter_id, 1));
+        assert_eq!(Err(invalid(counter_id)), manager.free(counter_id));
+        assert!(UnsafeBufferPosition::new(manager.reader(), counter_id).is_err());
+    }
+    assert_eq!(Ok(0), manager.get_counter_value(3));
+}
+
+#[test]
+fn test_counter_freed_when_closed_or_dropped() {
+    let mut manager = new_manager(&Arc::new(AtomicI64::new(0)), 0);
+    let mut counter = manager.new_counter("closed").unwrap();
+    let dropped = manager.new_counter("dropped").unwrap();
+    assert_eq!(2, manager.available_counter_capacity());
+
+    assert_eq!(Ok(()), counter.close());
+    assert_eq!(Ok(()), counter.close());
+    assert_eq!(Ok(RECORD_RECLAIMED), manager.get_counter_state(counter.id()));
+    drop(dropped);
+    assert_eq!(Ok(RECORD_RECLAIMED), manager.get_counter_state(1));
+    assert_eq!(4, manager.available_counter_capacity());
+
+    let reused = manager.new_counter("reused").unwrap();
+    manager.free(reused.id()).unwrap();
+    drop(reused);
+
+    let counter_id = manager.allocate("not owned").unwrap();
+    drop(AtomicCounter::new(manager.reader(), counter_id).unwrap());
+    assert_eq!(Ok(RECORD_ALLOCATED), manager.get_counter_state(counter_id));
+}
@@ -0,0 +1,103 @@
+use Ringo::agrona::deadline_timer_wheel::{DeadlineTimerWheel, NULL_DEADLINE};
+use Ringo::agrona::timer_wheel_error::TimerWheelError;
+
+const RESOLUTION: i64 = 1 << 20;
+
+fn poll_until<F>(timer_wheel: &mut DeadlineTimerWheel, now: &mut i64, end: i64, mut handler: F) where F: FnMut(i64, i64) -> bool {
+    while *now < end {
+        timer_wheel.poll(*now, &mut handler, i32::MAX);
+        *now += RESOLUTION;
+    }
+}
+
+#[test]
+fn test_timer_expires_on_deadline() {
+    let mut now = 0;
+    let mut timer_wheel = DeadlineTimerWheel::new(now, RESOLUTION, 1024);
+    let timer_id = timer_wheel.schedule_timer(now + 5 * RESOLUTION);
+    assert_eq!(5 * RESOLUTION, timer_wheel.deadline(timer_id));
+
+    let mut expired = Vec::new();
+    poll_until(&mut timer_wheel, &mut now, 16 * RESOLUTION, |time, id| {
+        expired.push((time, id));
+        true
+    });
+
+    assert_eq!(1, expired.len());
+    assert_eq!(timer_id, expired[0].1);
+    assert!(expired[0].0 >= 5 * RESOLUTION && expired[0].0 < 7 * RESOLUTION);
+    assert_eq!(0, timer_wheel.timer_count());
+    assert_eq!(NULL_DEADLINE, timer_wheel.deadline(timer_id));
+}
+
+#[test]
+fn test_cancelled_timer_does_not_expire() {
+    let mut now = 0;
+    let mut timer_wheel = DeadlineTimerWheel::new(now, RESOLUTION, 8);
+    let cancelled = timer_wheel.schedule_timer(3 * RESOLUTION);
+    let kept = timer_wheel.schedule_timer(3 * RESOLUTION);
+
+    assert!(timer_wheel.cancel_timer(cancelled));
+    assert!(!timer_wheel.cancel_timer(cancelled));
+    assert!(!timer_wheel.cancel_timer(-1));
+
+    let mut expired = Vec::new();
+    poll_until(&mut timer_wheel, &mut now, 32 * RESOLUTION, |_, id| {
+        expired.push(id);
+        true
+    });
+    assert_eq!(vec![kept], expired);
+}
+
+#[test]
+fn test_tick_allocation_grows_when_full() {
+    let mut now = 0;
+    let mut timer_wheel = DeadlineTimerWheel::new(now, RESOLUTION, 8);
+    let mut timer_ids: Vec<i64> = (0..40).map(|_| timer_wheel.schedule_timer(2 * RESOLUTION)).collect();
+    assert_eq!(64, timer_wheel.tick_allocation());
+    assert_eq!(40, timer_wheel.timer_count());
+
+    let mut scheduled = Vec::new();
+    timer_wheel.for_each(|deadline, id| scheduled.push((deadline, id)));
+    assert_eq!(timer_ids.iter().map(|&id| (2 * RESOLUTION, id)).collect::<Vec<_>>(), scheduled);
+
+    let mut expired = Vec::new();
+    poll_until(&mut timer_wheel, &mut now, 8 * RESOLUTION, |_, id| {
+        expired.push(id);
+        true
+    });
+    expired.sort();
+    timer_ids.sort();
+    assert_eq!(timer_ids, expired);
+}
+
+#[test]
+fn test_handler_keeps_timer_and_expiry_limit() {
+    let now = 4 * RESOLUTION;
+    let mut timer_wheel = DeadlineTimerWheel::new(0, RESOLUTION, 8);
+    timer_wheel.set_current_tick_time(now);
+    for _ in 0..3 {
+        timer_wheel.schedule_timer(now);
+    }
+
+    assert_eq!(0, timer_wheel.poll(now, |_, _| false, i32::MAX));
+    assert_eq!(3, timer_wheel.timer_count());
+    assert_eq!(2, timer_wheel.poll(now, |_, _| true, 2));
+    assert_eq!(1, timer_wheel.poll(now, |_, _| true, 2));
+    assert_eq!(0, timer_wheel.timer_count());
+}
+
+#[test]
+fn test_configuration_checked() {
+    assert!(DeadlineTimerWheel::try_new(0, 1, 1, 1).is_ok());
+    assert_eq!(Some(TimerWheelError::InvalidTicksPerWheel(10)), DeadlineTimerWheel::try_new(0, 8, 10, 16).err());
+    assert_eq!(Some(TimerWheelError::InvalidTickResolution(3)), DeadlineTimerWheel::try_new(0, 3, 16, 16).err());
+    assert_eq!(Some(TimerWheelError::InvalidTickAllocation(0)), DeadlineTimerWheel::try_new(0, 8, 16, 0).err());
+
+    let mut timer_wheel = DeadlineTimerWheel::new(0, 8, 16);
+    let timer_id = timer_wheel.schedule_timer(100);
+    assert_eq!(Err(TimerWheelError::TimersActive(1)), timer_wheel.reset_start_time(50));
+    timer_wheel.cancel_timer(timer_id);
+    assert_eq!(Ok(()), timer_wheel.reset_start_time(50));
+    assert_eq!(58, timer_wheel.current_tick_time());
+}
@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+use Ringo::agrona::concurrent::backoff_idle_strategy::BackoffIdleStrategy;
+use Ringo::agrona::concurrent::busy_spin_idle_strategy::BusySpinIdleStrategy;
+use Ringo::agrona::concurrent::idle_strategy::IdleStrategy;
+use Ringo::agrona::concurrent::no_op_idle_strategy::NoOpIdleStrategy;
+use Ringo::agrona::concurrent::sleeping_idle_strategy::SleepingIdleStrategy;
+use Ringo::agrona::concurrent::yielding_idle_strategy::YieldingIdleStrategy;
+
+fn idle_time(idle_strategy: &mut dyn IdleStrategy, work_count: i32) -> Duration {
+    let start = Instant::now();
+    idle_strategy.idle(work_count);
+    start.elapsed()
+}
+
+#[test]
+fn test_aliases() {
+    let idle_strategies: Vec<Box<dyn IdleStrategy>> = vec![
+        Box::new(BusySpinIdleStrategy),
+        Box::new(NoOpIdleStrategy),
+        Box::new(YieldingIdleStrategy),
+        Box::new(SleepingIdleStrategy::default()),
+        Box::new(BackoffIdleStrategy::default()),
+    ];
+    let aliases: Vec<_> = idle_strategies.iter().map(|idle_strategy| idle_strategy.alias()).collect();
+    assert_eq!(vec!["spin", "noop", "yield", "sleep-ns", "backoff"], aliases);
+}
+
+#[test]
+fn test_sleeping_only_when_no_work() {
+    let mut idle_strategy = SleepingIdleStrategy::new(20_000_000);
+    assert!(idle_time(&mut idle_strategy, 1) < Duration::from_millis(20));
+    assert!(idle_time(&mut idle_strategy, 0) >= Duration::from_millis(20));
+}
+
+#[test]
+fn test_backoff_parks_after_spinning_and_yielding() {
+    let mut idle_strategy = BackoffIdleStrategy::new(1, 1, 20_000_000, 40_000_000);
+
+    // enter spinning, spin, yield, then move to parking
+    for _ in 0..4 {
+        assert!(idle_time(&mut idle_strategy, 0) < Duration::from_millis(20));
+    }
+    assert!(idle_time(&mut idle_strategy, 0) >= Duration::from_millis(10));
+
+    idle_strategy.idle(1);
+    assert!(idle_time(&mut idle_strategy, 0) < Duration::from_millis(20));
+}
@@ -0,0 +1,99 @@
+use std::{ptr, slice};
+use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::record_descriptor::{ALIGNMENT, HEADER_LENGTH};
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer::{RingBuffer, INSUFFICIENT_CAPACITY, PADDING_MSG_TYPE_ID};
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_descriptor::*;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::DirectBuffer;
+
+// The fixtures were encoded by hand following Agrona's ring buffer algorithm, not written by a JVM,
+// so these tests pin Ringo's layout against that encoding only. They say nothing about Agrona
+// compatibility until benchmarks/java/regenerate-fixtures.sh has been run against Agrona 1.21.2
+// and its output committed here.
+const WRITTEN: &[u8] = include_bytes!("fixtures/one_to_one_written.bin");
+const WRAPPED: &[u8] = include_bytes!("fixtures/one_to_one_wrapped.bin");
+
+fn load(fixture: &[u8]) -> UnsafeBuffer {
+    let buffer = UnsafeBuffer::allocate_direct(fixture.len());
+    unsafe { ptr::copy_nonoverlapping(fixture.as_ptr(), buffer.byte_array(), fixture.len()) };
+    buffer
+}
+
+fn bytes(buffer: &UnsafeBuffer) -> &[u8] {
+    unsafe { slice::from_raw_parts(buffer.byte_array(), buffer.capacity() as usize) }
+}
+
+fn claim_longs(ring_buffer: &OneToOneRingBuffer, msg_type_id: i32, values: &[i64]) {
+    let index = ring_buffer.try_claim(msg_type_id, values.len() as i32 * 8).unwrap().index();
+    for (i, value) in values.iter().enumerate() {
+        ring_buffer.buffer().put_long(index + i as i32 * 8, *value);
+    }
+    ring_buffer.commit(index);
+}
+
+#[test]
+fn test_layout_constants() {
+    assert_eq!(8, HEADER_LENGTH);
+    assert_eq!(8, ALIGNMENT);
+    assert_eq!(-1, PADDING_MSG_TYPE_ID);
+    assert_eq!(-2, INSUFFICIENT_CAPACITY);
+    assert_eq!(128, TAIL_POSITION_OFFSET);
+    assert_eq!(256, HEAD_CACHE_POSITION_OFFSET);
+    assert_eq!(384, HEAD_POSITION_OFFSET);
+    assert_eq!(512, CORRELATION_COUNTER_OFFSET);
+    assert_eq!(640, CONSUMER_HEARTBEAT_OFFSET);
+    assert_eq!(768, TRAILER_LENGTH);
+    assert_eq!(WRITTEN.len() as i32, buffer_length(1024));
+}
+
+#[test]
+fn test_read_written_fixture() {
+    let ring_buffer = OneToOneRingBuffer::new(load(WRITTEN));
+    assert_eq!(1024, ring_buffer.capacity());
+    assert_eq!(56, ring_buffer.producer_position());
+    assert_eq!(1234567890, ring_buffer.consumer_heartbeat_time());
+
+    let mut messages = Vec::new();
+    ring_buffer.read(|msg_type_id, buffer, index, length| {
+        let value = match msg_type_id {
+            1 => buffer.get_int(index) as i64,
+            _ => buffer.get_long(index),
+        };
+        messages.push((msg_type_id, length, value));
+    });
+    assert_eq!(vec![(1, 4, 42), (2, 8, 0x0102030405060708), (3, 16, 7)], messages);
+    assert_eq!(56, ring_buffer.consumer_position());
+}
+
+#[test]
+fn test_write_matches_written_fixture() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::allocate_direct(buffer_length(1024) as usize));
+    let index = ring_buffer.try_claim(1, 4).unwrap().index();
+    ring_buffer.buffer().put_int(index, 42);
+    ring_buffer.commit(index);
+    claim_longs(&ring_buffer, 2, &[0x0102030405060708]);
+    claim_longs(&ring_buffer, 3, &[7, -1]);
+    ring_buffer.put_consumer_heartbeat_time(1234567890);
+
+    assert_eq!(WRITTEN, bytes(ring_buffer.buffer()));
+}
+
+#[test]
+fn test_wrapped_buffer_matches_fixture() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(128));
+    for seq in 1..=5 {
+        claim_longs(&ring_buffer, 1, &[seq]);
+    }
+    assert_eq!(5, ring_buffer.read(|_, _, _, _| {}));
+    claim_longs(&ring_buffer, 2, &[6]);
+    claim_longs(&ring_buffer, 3, &[7, 8]);
+    claim_longs(&ring_buffer, 4, &[9]);
+    assert_eq!(WRAPPED, bytes(ring_buffer.buffer()));
+
+    let ring_buffer = OneToOneRingBuffer::new(load(WRAPPED));
+    let mut messages = Vec::new();
+    while ring_buffer.size() > 0 {
+        ring_buffer.read(|msg_type_id, buffer, index, _| messages.push((msg_type_id, buffer.get_long(index))));
+    }
+    assert_eq!(vec![(2, 6), (3, 7), (4, 9)], messages);
+}
@@ -4,10 +4,21 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::thread;
 use std::time::Duration;
+use Ringo::agrona::concurrent::atomic_buffer::AtomicBuffer;
+use Ringo::agrona::concurrent::ringbuffer::many_to_one_ring_buffer::ManyToOneRingBuffer;
 use Ringo::agrona::concurrent::ringbuffer::one_to_one_ring_buffer::OneToOneRingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::record_descriptor::{encoded_msg_offset, length_offset, make_header, type_offset};
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_descriptor::TAIL_POSITION_OFFSET;
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_stats::RingBufferStats;
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_unblocker::RingBufferUnblocker;
+use Ringo::agrona::concurrent::status::counters_manager::CountersManager;
+use Ringo::agrona::concurrent::test_clock::TestClock;
+use Ringo::agrona::concurrent::status::counters_reader::{COUNTER_LENGTH, METADATA_LENGTH};
 use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
 use Ringo::agrona::concurrent::ringbuffer::ring_buffer::RingBuffer;
+use Ringo::agrona::concurrent::ringbuffer::ring_buffer_error::RingBufferError;
 use Ringo::agrona::direct_buffer::DirectBuffer;
+use Ringo::agrona::io_util;
 
 #[test]
 fn test_try_claim() {
@@ -20,8 +31,8 @@ fn test_try_claim() {
         //let mut tmp1 = Arc::clone(&ring_buffer);
         for i in 0..10 {
         //let producer = thread::spawn(|| {
-            let index = ring_buffer.try_claim(888, 32);
-            if index > 0 {
+            if let Ok(index) = ring_buffer.try_claim(888, 32) {
+                let index = index.index();
                 let mut buf = ring_buffer.buffer();
                 buf.put_int(index + 28, i + 1);
                 ring_buffer.commit(index);
@@ -42,3 +53,343 @@ fn test_try_claim() {
         //         "Unexpected result: {}", result.load(Ordering::SeqCst));
     });
 }
+
+#[test]
+fn test_many_to_one_concurrent_producers() {
+    const PRODUCERS: i32 = 4;
+    const MESSAGES_PER_PRODUCER: i32 = 10_000;
+
+    let ring_buffer = Arc::new(ManyToOneRingBuffer::new(UnsafeBuffer::new(4096)));
+
+    let producers: Vec<_> = (0..PRODUCERS).map(|producer_id| {
+        let ring_buffer = Arc::clone(&ring_buffer);
+        thread::spawn(move || {
+            let mut seq = 0;
+            while seq < MESSAGES_PER_PRODUCER {
+                if let Ok(index) = ring_buffer.try_claim(producer_id + 1, 8) {
+                    let index = index.index();
+                    let buf = ring_buffer.buffer();
+                    buf.put_int(index, producer_id);
+                    buf.put_int(index + 4, seq);
+                    ring_buffer.commit(index);
+                    seq += 1;
+                }
+            }
+        })
+    }).collect();
+
+    let mut next_seq = vec![0; PRODUCERS as usize];
+    let mut received = 0;
+    while received < PRODUCERS * MESSAGES_PER_PRODUCER {
+        received += ring_buffer.read(|msg_type_id, buffer, index, length| {
+            assert_eq!(8, length);
+            let producer_id = buffer.get_int(index);
+            assert_eq!(producer_id + 1, msg_type_id);
+            assert_eq!(next_seq[producer_id as usize], buffer.get_int(index + 4));
+            next_seq[producer_id as usize] += 1;
+        });
+    }
+
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    assert_eq!(ring_buffer.producer_position(), ring_buffer.consumer_position());
+}
+
+#[test]
+fn test_unblock_stalled_claim() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(1024));
+    let clock = Arc::new(TestClock::new(0));
+    let mut unblocker = RingBufferUnblocker::new(&ring_buffer, 100, Arc::clone(&clock));
+
+    assert!(ring_buffer.try_claim(1, 8).is_ok());
+    let index = ring_buffer.try_claim(2, 8).unwrap().index();
+    ring_buffer.buffer().put_int(index, 42);
+    ring_buffer.commit(index);
+
+    assert_eq!(0, ring_buffer.read(|_, _, _, _| {}));
+    clock.set_time(50);
+    assert!(!unblocker.poll());
+    assert_eq!(50, ring_buffer.consumer_heartbeat_time());
+    clock.set_time(101);
+    assert!(unblocker.poll());
+
+    let mut values = Vec::new();
+    assert_eq!(1, ring_buffer.read(|msg_type_id, buffer, index, _| {
+        values.push((msg_type_id, buffer.get_int(index)));
+    }));
+    assert_eq!(vec![(2, 42)], values);
+    clock.set_time(300);
+    assert!(!unblocker.poll());
+}
+
+#[test]
+fn test_unblock_keeps_record_of_live_producer() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(1024));
+    let buffer = ring_buffer.buffer();
+    let tail_position_index = ring_buffer.capacity() + TAIL_POSITION_OFFSET;
+
+    // a slow producer which has moved the tail but not yet written the header, then commits
+    buffer.put_long_ordered(tail_position_index, 16);
+    assert!(ring_buffer.unblock());
+    buffer.put_int_ordered(length_offset(0), -12);
+    buffer.put_int(encoded_msg_offset(0), 42);
+    buffer.put_int(type_offset(0), 7);
+    buffer.put_int_ordered(length_offset(0), 12);
+
+    let mut messages = Vec::new();
+    assert_eq!(1, ring_buffer.read(|msg_type_id, buffer, index, _| messages.push((msg_type_id, buffer.get_int(index)))));
+    assert_eq!(vec![(7, 42)], messages);
+
+    // a batch whose second record is written before the first header is not padded over
+    buffer.put_long_ordered(tail_position_index, 48);
+    buffer.put_int(encoded_msg_offset(32), 43);
+    buffer.put_long(32, make_header(12, 8));
+    assert!(!ring_buffer.unblock());
+    buffer.put_int(encoded_msg_offset(16), 44);
+    buffer.put_long_ordered(16, make_header(12, 9));
+
+    messages.clear();
+    assert_eq!(2, ring_buffer.read(|msg_type_id, buffer, index, _| messages.push((msg_type_id, buffer.get_int(index)))));
+    assert_eq!(vec![(9, 44), (8, 43)], messages);
+    assert_eq!(0, ring_buffer.size());
+}
+
+#[test]
+fn test_ring_buffer_shared_through_mapped_file() {
+    let path = io_util::tmp_dir_name().join(format!("ringo-test-{}.rb", std::process::id()));
+
+    let producer = OneToOneRingBuffer::new(UnsafeBuffer::map_new_file(&path, 1024).unwrap());
+    let consumer = OneToOneRingBuffer::new(UnsafeBuffer::map_existing_file(&path).unwrap());
+    UnsafeBuffer::unlink(&path).unwrap();
+    assert_eq!(producer.capacity(), consumer.capacity());
+
+    for i in 0..3 {
+        let index = producer.try_claim(7, 8).unwrap().index();
+        producer.buffer().put_long(index, i);
+        producer.commit(index);
+    }
+
+    let mut values = Vec::new();
+    assert_eq!(3, consumer.read(|_, buffer, index, _| values.push(buffer.get_long(index))));
+    assert_eq!(vec![0, 1, 2], values);
+    assert_eq!(producer.consumer_position(), producer.producer_position());
+}
+
+#[test]
+fn test_split_producer_and_consumer() {
+    let (mut producer, mut consumer) = OneToOneRingBuffer::new(UnsafeBuffer::new(1024)).split();
+
+    let writer = thread::spawn(move || {
+        let mut value = 0i64;
+        while value < 1000 {
+            if let Ok(mut claim) = producer.try_claim(1, 8) {
+                claim.copy_from_slice(&value.to_ne_bytes());
+                if value % 10 == 0 {
+                    drop(claim);
+                } else {
+                    claim.commit().unwrap();
+                }
+                value += 1;
+            }
+        }
+    });
+
+    let mut values = Vec::new();
+    while values.len() < 900 {
+        consumer.read(|_, buffer, index, _| values.push(buffer.get_long(index)));
+    }
+    writer.join().unwrap();
+
+    let expected: Vec<i64> = (0..1000).filter(|value| value % 10 != 0).collect();
+    assert_eq!(expected, values);
+    assert_eq!(0, consumer.size());
+}
+
+#[test]
+fn test_claim_aborted_on_panic() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(1024));
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut claim = ring_buffer.claim(1, 16).unwrap();
+        claim[..8].copy_from_slice(&1i64.to_ne_bytes());
+        panic!("failed to encode");
+    }));
+    assert!(result.is_err());
+
+    let mut claim = ring_buffer.claim(2, 8).unwrap();
+    assert_eq!(8, claim.len());
+    claim.copy_from_slice(&2i64.to_ne_bytes());
+    claim.commit().unwrap();
+
+    let mut messages = Vec::new();
+    ring_buffer.read(|msg_type_id, buffer, index, _| messages.push((msg_type_id, buffer.get_long(index))));
+    assert_eq!(vec![(2, 2)], messages);
+}
+
+#[test]
+fn test_claim_released_by_unblock() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(1024));
+
+    let claim = ring_buffer.claim(1, 8).unwrap();
+    assert!(ring_buffer.unblock());
+    assert_eq!(Err(RingBufferError::ClaimAborted), claim.commit());
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _claim = ring_buffer.claim(1, 8).unwrap();
+        assert!(ring_buffer.unblock());
+        panic!("failed to encode");
+    }));
+    assert!(result.is_err());
+    assert_eq!(0, ring_buffer.read(|_, _, _, _| {}));
+    assert_eq!(0, ring_buffer.size());
+}
+
+#[test]
+fn test_errors_reported_as_results() {
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(64));
+    let src = UnsafeBuffer::new(64);
+
+    assert_eq!(Err(RingBufferError::InvalidTypeId(0)), ring_buffer.try_write(0, &src, 0, 8));
+    assert_eq!(Err(RingBufferError::MessageTooLong { length: 16, max_msg_length: 8 }), ring_buffer.try_write(1, &src, 0, 16));
+    assert_eq!(Err(RingBufferError::InvalidMessageLength(-1)), ring_buffer.try_claim(1, -1));
+
+    let index = ring_buffer.try_claim(1, 8).unwrap().index();
+    assert_eq!(Ok(()), ring_buffer.try_commit(index));
+    assert_eq!(Err(RingBufferError::ClaimCommitted), ring_buffer.try_abort(index));
+    assert_eq!(Err(RingBufferError::InvalidIndex(0)), ring_buffer.try_commit(0));
+
+    while ring_buffer.try_write(1, &src, 0, 8).is_ok() {}
+    assert_eq!(Err(RingBufferError::InsufficientCapacity), ring_buffer.try_write(1, &src, 0, 8));
+    assert!(!ring_buffer.write(1, &src, 0, 8));
+
+    assert_eq!(Err(RingBufferError::InvalidCapacity(1000)), OneToOneRingBuffer::try_new(UnsafeBuffer::allocate_direct(1000)).map(|_| ()));
+}
+
+#[test]
+fn test_write_vectored_into_one_record() {
+    let ring_buffer = ManyToOneRingBuffer::new(UnsafeBuffer::new(1024));
+    let header = 7i32.to_ne_bytes();
+    let payload = 42i32.to_ne_bytes();
+
+    assert!(ring_buffer.write_vectored(3, &[&header, &payload]));
+
+    let mut messages = Vec::new();
+    ring_buffer.read(|msg_type_id, buffer, index, length| {
+        messages.push((msg_type_id, length, buffer.get_int(index), buffer.get_int(index + 4)));
+    });
+    assert_eq!(vec![(3, 8, 7, 42)], messages);
+}
+
+#[test]
+fn test_write_batch_published_together() {
+    fn check<R: RingBuffer>(ring_buffer: R) {
+        let values: Vec<[u8; 8]> = (0..20i64).map(|value| value.to_ne_bytes()).collect();
+        let batch: Vec<(i32, &[u8])> = values.iter().map(|bytes| (1, &bytes[..4])).collect();
+
+        // batches of 20 records of 16 bytes, the fourth wraps around the 1024 byte buffer
+        for _ in 0..4 {
+            assert!(ring_buffer.write_batch(&batch));
+            let mut received = Vec::new();
+            while ring_buffer.size() > 0 {
+                ring_buffer.read(|_, buffer, index, length| {
+                    assert_eq!(4, length);
+                    received.push(buffer.get_int(index));
+                });
+            }
+            assert_eq!((0..20).collect::<Vec<i32>>(), received);
+        }
+
+        let too_long: Vec<(i32, &[u8])> = values.iter().chain(values.iter()).map(|bytes| (1, &bytes[..])).collect();
+        assert_eq!(Err(RingBufferError::BatchTooLong { length: 512, max_batch_length: 504 }), ring_buffer.try_write_batch(&too_long[..32]));
+        assert_eq!(Err(RingBufferError::InvalidTypeId(0)), ring_buffer.try_write_batch(&[(1, &[]), (0, &[])]));
+    }
+
+    check(OneToOneRingBuffer::new(UnsafeBuffer::new(1024)));
+    check(ManyToOneRingBuffer::new(UnsafeBuffer::new(1024)));
+}
+
+#[test]
+fn test_drain_advances_head_once() {
+    fn check<R: RingBuffer>(ring_buffer: R) {
+        for value in 1..=3i64 {
+            assert!(ring_buffer.write_vectored(value as i32, &[&value.to_ne_bytes(), &[7u8]]));
+        }
+
+        let mut drain = ring_buffer.drain();
+        let mut messages = Vec::new();
+        while let Some(message) = drain.next() {
+            assert_eq!(9, message.as_bytes().len());
+            messages.push((message.type_id(), message.get_long(0), message.get_byte(8)));
+            if messages.len() == 2 {
+                break;
+            }
+        }
+        assert_eq!(0, ring_buffer.consumer_position());
+        drop(drain);
+        assert_eq!(vec![(1, 1, 7), (2, 2, 7)], messages);
+        assert_eq!(48, ring_buffer.consumer_position());
+
+        let mut last = None;
+        assert_eq!(1, ring_buffer.read_messages(|message| last = Some(message.get_long(0)), 10));
+        assert_eq!(Some(3), last);
+        assert!(ring_buffer.drain().next().is_none());
+    }
+
+    check(OneToOneRingBuffer::new(UnsafeBuffer::new(1024)));
+    check(ManyToOneRingBuffer::new(UnsafeBuffer::new(1024)));
+}
+
+#[test]
+fn test_stats_counted() {
+    let mut counters_manager = CountersManager::new(
+        UnsafeBuffer::allocate_direct(8 * METADATA_LENGTH as usize),
+        UnsafeBuffer::allocate_direct(8 * COUNTER_LENGTH as usize));
+    let stats = RingBufferStats::new(&mut counters_manager, "orders").unwrap();
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(128)).with_stats(stats);
+
+    // 16 byte records, the eighth does not fit as there must be space for a following header
+    for value in 0..8i64 {
+        assert_eq!(value < 7, ring_buffer.write_vectored(1, &[&value.to_ne_bytes()]));
+    }
+    assert_eq!(7, ring_buffer.read(|_, _, _, _| {}));
+
+    // wraps around with padding at the end of the buffer, and is followed by an aborted claim
+    assert!(ring_buffer.write_vectored(1, &[&[0; 16]]));
+    drop(ring_buffer.claim(1, 8).unwrap());
+    assert_eq!(0, ring_buffer.read(|_, _, _, _| {}));
+    assert_eq!(1, ring_buffer.read(|_, _, _, _| {}));
+
+    let mut values = Vec::new();
+    counters_manager.reader().for_each_value(|value, _, label| values.push((label.to_string(), value)));
+    assert_eq!(vec![
+        ("orders: messages written".to_string(), 8),
+        ("orders: bytes written".to_string(), 72),
+        ("orders: claim failures".to_string(), 1),
+        ("orders: padding records".to_string(), 2),
+        ("orders: aborts".to_string(), 1),
+        ("orders: max fill level".to_string(), 112),
+    ], values);
+}
+
+#[test]
+fn test_consumer_heartbeat_stamped_on_read() {
+    let clock = Arc::new(TestClock::new(1_000));
+    let ring_buffer = OneToOneRingBuffer::new(UnsafeBuffer::new(1024))
+        .with_consumer_heartbeat(Arc::clone(&clock));
+    let (mut producer, mut consumer) = ring_buffer.split();
+
+    assert!(!producer.is_consumer_alive(100));
+
+    consumer.read(|_, _, _, _| {});
+    assert_eq!(1_000, producer.consumer_heartbeat_time());
+    clock.advance_ms(100);
+    assert!(producer.is_consumer_alive(100));
+
+    clock.advance_ms(1);
+    assert!(!producer.is_consumer_alive(100));
+
+    drop(consumer.drain());
+    assert!(producer.is_consumer_alive(0));
+    assert!(producer.write_vectored(1, &[&[1, 2, 3]]));
+}
@@ -0,0 +1,72 @@
+use Ringo::agrona::buffer_error::BufferError;
+use Ringo::agrona::concurrent::unsafe_buffer::UnsafeBuffer;
+use Ringo::agrona::direct_buffer::{DirectBuffer, STR_HEADER_LEN};
+
+#[test]
+fn test_ascii_string_round_trip() {
+    let buffer = UnsafeBuffer::new(64);
+
+    assert_eq!(STR_HEADER_LEN + 5, buffer.put_string_ascii(4, "35=D|"));
+    assert_eq!(5, buffer.get_int(4));
+    assert_eq!(Ok("35=D|".to_string()), buffer.get_string_ascii(4));
+    assert_eq!(Ok("35=".to_string()), buffer.get_string_without_length_ascii(4 + STR_HEADER_LEN, 3));
+
+    // the length prefix need not be aligned
+    let mut dst = String::from("tag ");
+    assert_eq!(STR_HEADER_LEN + 5, buffer.put_string_ascii(41, "35=D|"));
+    assert_eq!(Ok(5), buffer.get_string_ascii_into(41, &mut dst));
+    assert_eq!("tag 35=D|", dst);
+
+    assert_eq!(STR_HEADER_LEN + 4, buffer.put_string_ascii(16, "café"));
+    assert_eq!(Ok("caf?".to_string()), buffer.get_string_ascii(16));
+    assert_eq!(2, buffer.put_string_without_length_ascii0(32, "€uro", 1, 2));
+    assert_eq!(Ok("ur".to_string()), buffer.get_string_without_length_ascii(32, 2));
+}
+
+#[test]
+fn test_utf8_string_round_trip() {
+    let buffer = UnsafeBuffer::new(64);
+
+    let value = "Zürich €";
+    let length = buffer.put_string_utf8(0, value);
+    assert_eq!(STR_HEADER_LEN + value.len() as i32, length);
+    assert_eq!(Ok(value), buffer.get_str_utf8(0));
+    assert_eq!(Ok(value.to_string()), buffer.get_string_utf8(0));
+    assert_eq!(Ok("Zü".to_string()), buffer.get_string_utf8_0(0, 3));
+
+    let mut dst = String::with_capacity(32);
+    assert_eq!(Ok(value.len() as i32), buffer.get_string_utf8_into(0, &mut dst));
+    assert_eq!(value, dst);
+
+    // splitting 'ü' leaves invalid UTF-8
+    assert!(matches!(buffer.get_str_without_length_utf8(STR_HEADER_LEN, 2), Err(BufferError::InvalidUtf8 { index: 4, .. })));
+    assert!(buffer.get_string_without_length_utf8(STR_HEADER_LEN, 2).is_err());
+}
+
+#[test]
+fn test_utf8_max_encoded_length() {
+    let buffer = UnsafeBuffer::new(64);
+
+    assert_eq!(Ok(STR_HEADER_LEN + 3), buffer.put_string_utf8_1(0, "€", 3));
+    assert_eq!(Err(BufferError::EncodedLengthTooLong { length: 3, max_length: 2 }), buffer.put_string_utf8_1(8, "€", 2));
+    assert_eq!(Ok(""), buffer.get_str_utf8(8));
+}
+
+#[test]
+fn test_corrupt_length_prefix() {
+    let buffer = UnsafeBuffer::allocate_direct(64);
+
+    buffer.put_int_be(0, -1);
+    assert_eq!(Err(BufferError::IndexOutOfBounds { index: 4, length: -1, capacity: 64 }), buffer.get_string_ascii(0));
+    assert_eq!(Err(BufferError::IndexOutOfBounds { index: 4, length: -1, capacity: 64 }), buffer.get_str_utf8(0));
+
+    buffer.put_string_utf8(8, "long");
+    buffer.put_int_le(8, 61);
+    assert_eq!(Err(BufferError::IndexOutOfBounds { index: 12, length: 61, capacity: 64 }), buffer.get_string_utf8(8));
+    assert_eq!(Err(BufferError::IndexOutOfBounds { index: 62, length: 4, capacity: 64 }), buffer.get_str_utf8(62));
+    assert_eq!(Err(BufferError::IndexOutOfBounds { index: 4, length: -1, capacity: 64 }), buffer.get_string_without_length_ascii(4, -1));
+
+    let mut dst = String::from("kept");
+    assert!(buffer.get_string_ascii_into(0, &mut dst).is_err());
+    assert_eq!("kept", dst);
+}
@@ -0,0 +1,51 @@
+#![cfg(target_os = "linux")]
+
+use std::io;
+use std::thread;
+use Ringo::agrona::concurrent::agent::{Agent, AgentError};
+use Ringo::agrona::concurrent::agent_runner::AgentRunner;
+use Ringo::agrona::concurrent::no_op_idle_strategy::NoOpIdleStrategy;
+use Ringo::agrona::concurrent::thread_affinity::{current_cpus, parse_cpu_list, ThreadAffinity};
+
+#[test]
+fn test_parse_affinity() {
+    assert_eq!(vec![0, 2, 3, 4, 7], parse_cpu_list("0,2-4, 7").unwrap());
+    assert_eq!(ThreadAffinity::new(vec![2, 4, 5]).with_fifo_priority(50), "cpus=2,4-5;fifo=50".parse().unwrap());
+    assert_eq!(ThreadAffinity::new(vec![1]), "cpus=1".parse().unwrap());
+    assert_eq!(io::ErrorKind::InvalidInput, "cpus=3-1".parse::<ThreadAffinity>().unwrap_err().kind());
+    assert_eq!(io::ErrorKind::InvalidInput, "cores=1".parse::<ThreadAffinity>().unwrap_err().kind());
+}
+
+#[test]
+fn test_pin_current_thread() {
+    thread::spawn(|| {
+        let cpu = current_cpus().unwrap()[0];
+        ThreadAffinity::new(vec![cpu]).apply().unwrap();
+        assert_eq!(vec![cpu], current_cpus().unwrap());
+
+        assert_eq!(io::ErrorKind::InvalidInput, ThreadAffinity::new(vec![1 << 20]).apply().unwrap_err().kind());
+        assert_eq!(io::ErrorKind::InvalidInput, ThreadAffinity::default().with_fifo_priority(1000).apply().unwrap_err().kind());
+    }).join().unwrap();
+}
+
+struct PinnedAgent;
+
+impl Agent for PinnedAgent {
+    fn do_work(&mut self) -> Result<i32, AgentError> {
+        Err(AgentError::Terminate)
+    }
+
+    fn role_name(&self) -> &str {
+        "pinned"
+    }
+}
+
+#[test]
+fn test_runner_reports_affinity_errors() {
+    let error = AgentRunner::start_on_thread_with_affinity(PinnedAgent, NoOpIdleStrategy, |_: &AgentError| {}, ThreadAffinity::new(vec![1 << 20]));
+    assert_eq!(io::ErrorKind::InvalidInput, error.err().unwrap().kind());
+
+    let cpus = current_cpus().unwrap();
+    let mut runner = AgentRunner::start_on_thread_with_affinity(PinnedAgent, NoOpIdleStrategy, |_: &AgentError| {}, ThreadAffinity::new(cpus)).unwrap();
+    runner.close().unwrap();
+}